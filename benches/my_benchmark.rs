@@ -18,10 +18,20 @@ fn decrypt() {
     pf.decrypt("bmodzbxdnabekudmuixmmouvif");
 }
 
+#[inline]
+fn construction_heavy() {
+    // Many instances are constructed but only one is ever used
+    let ciphers: Vec<Playfair> = (0..100)
+        .map(|_| Playfair::new("playfair example"))
+        .collect();
+    ciphers[0].encrypt("hide the gold in the tree stump");
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
-    c.bench_function("keyword generation", |b| b.iter(|| keyword_generation()));
-    c.bench_function("encrypt", |b| b.iter(|| encrypt()));
-    c.bench_function("decrypt", |b| b.iter(|| decrypt()));
+    c.bench_function("keyword generation", |b| b.iter(keyword_generation));
+    c.bench_function("encrypt", |b| b.iter(encrypt));
+    c.bench_function("decrypt", |b| b.iter(decrypt));
+    c.bench_function("construction heavy", |b| b.iter(construction_heavy));
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -0,0 +1,826 @@
+//! Cryptanalysis helpers, useful for exercises in identifying and breaking the Playfair cipher.
+
+use std::collections::HashSet;
+
+use crate::{
+    Bigram, BigramMode, Cipher, Keyword, Playfair, SeparatorStrategy, ALPHABET, DEFAULT_PAD,
+    DEFAULT_SEPARATOR,
+};
+
+/// Count the occurrences of each letter 'a' through 'z' in a text, ignoring case and any
+/// non-letter characters. Index 0 holds the count for 'a', index 25 the count for 'z'.
+///
+/// Applied to Playfair ciphertext this shows a flatter distribution than the plaintext it came
+/// from, and a count of 0 for 'j', which never appears in the matrix.
+pub fn letter_frequency(text: &str) -> [usize; 26] {
+    let mut counts = [0; 26];
+
+    for c in text.chars().filter(char::is_ascii_alphabetic) {
+        counts[(c.to_ascii_lowercase() as u8 - b'a') as usize] += 1;
+    }
+
+    counts
+}
+
+/// Compute the Index of Coincidence of a text: the probability that two letters drawn at random
+/// (without replacement) from the text are the same. Only the letters 'a' through 'z' are
+/// considered, ignoring case.
+///
+/// English plaintext sits around 0.066 and uniformly random text around 0.038, while Playfair
+/// ciphertext typically lands in between, which is a useful signal when identifying the cipher.
+/// Texts with fewer than two letters have an IC of 0.
+pub fn index_of_coincidence(text: &str) -> f64 {
+    let counts = letter_frequency(text);
+    let total: usize = counts.iter().sum();
+
+    if total < 2 {
+        return 0.0;
+    }
+
+    let coincidences: usize = counts.iter().map(|n| n * n.saturating_sub(1)).sum();
+
+    coincidences as f64 / (total * (total - 1)) as f64
+}
+
+/// Decrypt a ciphertext along with variants of it that have had a single transmission error,
+/// showing how an error propagates through decryption. The first entry is the straight
+/// decryption, followed by at most `max_swaps` decryptions, each with one adjacent pair of
+/// ciphertext letters swapped (starting from the front of the ciphertext).
+pub fn decrypt_variants(pf: &Playfair, ciphertext: &str, max_swaps: usize) -> Vec<String> {
+    let letters: Vec<char> = Playfair::normalize(ciphertext).collect();
+    let mut variants = vec![pf.decrypt(ciphertext)];
+
+    for idx in 0..letters.len().saturating_sub(1) {
+        if variants.len() > max_swaps {
+            break;
+        }
+
+        // Swapping two identical letters wouldn't change anything
+        if letters[idx] == letters[idx + 1] {
+            continue;
+        }
+
+        let mut swapped = letters.clone();
+        swapped.swap(idx, idx + 1);
+        variants.push(pf.decrypt(&swapped.iter().collect::<String>()));
+    }
+
+    variants
+}
+
+/// List the bigrams that encrypt to themselves under a cipher's matrix, a weakness since those
+/// pairs would pass through encryption unchanged. Every pair of letters from the matrix alphabet
+/// is checked, including doubled letters.
+///
+/// By construction none of the three rules can map a pair onto itself for a valid matrix, so
+/// this is expected to be empty, but it confirms that for a given (possibly transformed) matrix.
+pub fn fixed_point_bigrams(pf: &Playfair) -> Vec<Bigram> {
+    all_bigrams()
+        .filter(|&bigram| pf.encrypt_bigram(bigram) == bigram)
+        .collect()
+}
+
+/// Map every bigram of the matrix alphabet to the bigram it encrypts to under a cipher, the
+/// complete substitution table of the key. The table is indexed by the positions of the two
+/// letters in the alphabet "abcdefghiklmnopqrstuvwxyz" (without 'j'), so the entry for `(a, b)`
+/// is at `[0][1]`.
+pub fn transition_matrix(pf: &Playfair) -> [[Bigram; 25]; 25] {
+    let letters: Vec<char> = ALPHABET.chars().collect();
+
+    std::array::from_fn(|a| std::array::from_fn(|b| pf.map_bigram((letters[a], letters[b]))))
+}
+
+/// List the bigrams that encryption under a cipher can never output, in alphabetical order and
+/// lowercase, e.g. for rejecting ciphertext that can't have come from the key.
+///
+/// Doubled letters are seperated before encryption (unless the cipher uses
+/// [BigramMode::NoSeparator]), with one exception: the [DEFAULT_SEPARATOR] and [DEFAULT_PAD] are
+/// both 'x', so an 'x' that is doubled or ends an odd length input forms the pair ('x', 'x').
+/// Every other bigram maps onto a distinct bigram of two different letters, so the unreachable
+/// outputs are the doubled letters other than the encryption of ('x', 'x'), unless the cipher's
+/// [SeparatorStrategy::Priority] keeps 'x' from ever being doubled.
+pub fn unreachable_outputs(pf: &Playfair) -> Vec<Bigram> {
+    let doubled_x = (DEFAULT_SEPARATOR, DEFAULT_PAD);
+    let reachable: HashSet<Bigram> = all_bigrams()
+        .filter(|&(a, b)| {
+            a != b
+                || pf.bigram_mode == BigramMode::NoSeparator
+                || ((a, b) == doubled_x && pf.separator_strategy != SeparatorStrategy::Priority)
+        })
+        .map(|bigram| {
+            let (a, b) = pf.encrypt_bigram(bigram);
+            (a.to_ascii_lowercase(), b.to_ascii_lowercase())
+        })
+        .collect();
+
+    all_bigrams()
+        .filter(|bigram| !reachable.contains(bigram))
+        .collect()
+}
+
+/// Find short keywords whose matrix starts with the given first row, e.g. for designing a puzzle
+/// around a known part of the grid. Gives an empty list if the row isn't made of distinct letters
+/// of the matrix alphabet.
+///
+/// The first row holds the first 5 distinct letters of the keyword followed by the alphabet, so
+/// a prefix of the row works on its own if the alphabet then fills in the rest of the row, e.g.
+/// "ab" for the row "abcde". Beyond the row a keyword can continue with any of the other 20
+/// letters in any order, 20!/(20-n)! keywords for n extra letters, so the list is capped at
+/// those with at most one extra letter. Shorter keywords come first.
+pub fn keywords_fixing_first_row(row: [char; 5]) -> Vec<String> {
+    let row = row.map(|c| c.to_ascii_lowercase());
+
+    if row
+        .iter()
+        .enumerate()
+        .any(|(idx, c)| !ALPHABET.contains(*c) || row[..idx].contains(c))
+    {
+        return Vec::new();
+    }
+
+    let mut keywords: Vec<String> = (0..=5)
+        .filter(|&len| {
+            // The letters the alphabet adds after this prefix of the row
+            let fill = ALPHABET.chars().filter(|c| !row[..len].contains(c));
+            fill.zip(&row[len..]).all(|(a, b)| a == *b)
+        })
+        .map(|len| row[..len].iter().collect())
+        .collect();
+
+    let row: String = row.iter().collect();
+    keywords.extend(
+        ALPHABET
+            .chars()
+            .filter(|c| !row.contains(*c))
+            .map(|c| format!("{row}{c}")),
+    );
+
+    keywords
+}
+
+/// The redundancy of English in bits per letter: the log2(26) ≈ 4.7 bits a letter could carry,
+/// less the roughly 1.5 bits it actually carries.
+const ENGLISH_REDUNDANCY: f64 = 3.2;
+
+/// Estimate the unicity distance of a cipher's key, the number of ciphertext letters after which
+/// only one key is expected to give a meaningful decryption, so that the key is in principle
+/// determined. This is the classical estimate of the key's entropy over the redundancy of
+/// English, about 3.2 bits per letter.
+///
+/// The keyspace is taken to be the matrices generated by keywords with as many distinct letters
+/// as the shortest keyword for this matrix (see [Keyword::minimal_for]), as an attacker guessing
+/// the keyword would search, rather than all 25! arrangements. For k letters there are
+/// 25!/(25-k)! such keywords, each equally likely. Keys that give equivalent ciphers, such as
+/// matrices with their rows rotated, aren't accounted for, so this slightly overestimates.
+pub fn unicity_distance(pf: &Playfair) -> f64 {
+    let letters = Keyword::minimal_for(pf.matrix()).map_or(25, |kw| kw.len());
+
+    // log2(25! / (25 - k)!)
+    let entropy: f64 = (25 - letters + 1..=25).map(|n| (n as f64).log2()).sum();
+
+    entropy / ENGLISH_REDUNDANCY
+}
+
+/// Count the fewest swaps of two cells that turn one cipher's matrix into the other's, giving a
+/// measure of how far a guessed key is from the real one. The letters are compared regardless
+/// of case. Identical matrices are 0 apart, and no two matrices are more than 24 apart.
+pub fn key_distance(from: &Playfair, to: &Playfair) -> usize {
+    let cells = |pf: &Playfair| -> Vec<u8> { pf.to_bytes().to_ascii_lowercase().to_vec() };
+    let (from, to) = (cells(from), cells(to));
+
+    // Where each cell of `from` needs to move to, a permutation whose cycles of length n each
+    // take n - 1 swaps to put in place
+    let target: Vec<usize> = from
+        .iter()
+        .map(|c| {
+            to.iter()
+                .position(|d| d == c)
+                .expect("matrices hold the same letters")
+        })
+        .collect();
+
+    let mut visited = [false; 25];
+    let mut cycles = 0;
+
+    for start in 0..25 {
+        if visited[start] {
+            continue;
+        }
+
+        cycles += 1;
+        let mut idx = start;
+        while !visited[idx] {
+            visited[idx] = true;
+            idx = target[idx];
+        }
+    }
+
+    25 - cycles
+}
+
+/// Count the positions at which two ciphertexts differ, e.g. the same plaintext encrypted under
+/// two keys, to show how sensitive the output is to the key. Gives `None` if the ciphertexts
+/// have different lengths, since they can't then be compared position by position.
+pub fn ciphertext_distance(a: &str, b: &str) -> Option<usize> {
+    if a.chars().count() != b.chars().count() {
+        return None;
+    }
+
+    Some(a.chars().zip(b.chars()).filter(|(x, y)| x != y).count())
+}
+
+impl Playfair {
+    /// Decrypt a ciphertext and restore the 'j's that encryption turned into 'i's, using a
+    /// dictionary of lowercase words. Wherever the plaintext holds a dictionary word containing
+    /// a 'j' with its 'j's written as 'i's (e.g. "iump" for "jump"), those 'i's become 'j's
+    /// again. Words whose 'i' spelling is also in the dictionary are ambiguous and left as-is.
+    ///
+    /// Where matches overlap, the longest word wins, and words of the same length are tried in
+    /// alphabetical order, so the result doesn't depend on the order of the set.
+    pub fn decrypt_resolve_j(&self, ciphertext: &str, dict: &HashSet<String>) -> String {
+        let mut plaintext: Vec<char> = self.decrypt(ciphertext).chars().collect();
+
+        let mut words: Vec<&String> = dict.iter().filter(|w| w.contains('j')).collect();
+        words.sort_by(|a, b| {
+            b.chars()
+                .count()
+                .cmp(&a.chars().count())
+                .then_with(|| a.cmp(b))
+        });
+
+        for word in words {
+            let spelled = word.replace('j', "i");
+            if dict.contains(&spelled) {
+                continue;
+            }
+
+            let word: Vec<char> = word.chars().collect();
+            let spelled: Vec<char> = spelled.chars().collect();
+
+            for start in 0..(plaintext.len() + 1).saturating_sub(word.len()) {
+                let window = &mut plaintext[start..start + word.len()];
+
+                if window
+                    .iter()
+                    .map(char::to_ascii_lowercase)
+                    .eq(spelled.iter().copied())
+                {
+                    for (c, &original) in window.iter_mut().zip(&word) {
+                        if original == 'j' {
+                            // Keep the case of the decryption
+                            *c = if c.is_ascii_uppercase() { 'J' } else { 'j' };
+                        }
+                    }
+                }
+            }
+        }
+
+        plaintext.into_iter().collect()
+    }
+}
+
+/// Every ordered pair of letters from the matrix alphabet, doubled letters included.
+fn all_bigrams() -> impl Iterator<Item = Bigram> {
+    ALPHABET
+        .chars()
+        .flat_map(|a| ALPHABET.chars().map(move |b| (a, b)))
+}
+
+/// The most common English digraphs, with their approximate frequency as a percentage of all
+/// digraphs in English text. Together they make up about half of all digraphs.
+const DIGRAMS: &[(Bigram, f64)] = &[
+    (('t', 'h'), 3.56),
+    (('h', 'e'), 3.07),
+    (('i', 'n'), 2.43),
+    (('e', 'r'), 2.05),
+    (('a', 'n'), 1.99),
+    (('r', 'e'), 1.85),
+    (('o', 'n'), 1.76),
+    (('a', 't'), 1.49),
+    (('e', 'n'), 1.45),
+    (('n', 'd'), 1.35),
+    (('t', 'i'), 1.34),
+    (('e', 's'), 1.34),
+    (('o', 'r'), 1.28),
+    (('t', 'e'), 1.20),
+    (('o', 'f'), 1.17),
+    (('e', 'd'), 1.17),
+    (('i', 's'), 1.13),
+    (('i', 't'), 1.12),
+    (('a', 'l'), 1.09),
+    (('a', 'r'), 1.07),
+    (('s', 't'), 1.05),
+    (('t', 'o'), 1.04),
+    (('n', 't'), 1.04),
+    (('n', 'g'), 0.95),
+    (('s', 'e'), 0.93),
+    (('h', 'a'), 0.93),
+    (('a', 's'), 0.87),
+    (('o', 'u'), 0.87),
+    (('i', 'o'), 0.83),
+    (('l', 'e'), 0.83),
+    (('v', 'e'), 0.83),
+    (('c', 'o'), 0.79),
+    (('m', 'e'), 0.79),
+    (('d', 'e'), 0.76),
+    (('h', 'i'), 0.76),
+    (('r', 'i'), 0.73),
+    (('r', 'o'), 0.73),
+    (('i', 'c'), 0.70),
+    (('n', 'e'), 0.69),
+    (('e', 'a'), 0.69),
+    (('r', 'a'), 0.69),
+    (('c', 'e'), 0.65),
+];
+
+/// The log probability given to a digraph that isn't in [DIGRAMS], a little below that of the
+/// rarest digraph in the table.
+const DIGRAM_FLOOR: f64 = -3.0;
+
+/// The log (base 10) probability of a digraph in English, from an embedded table of the most
+/// common English digraphs, ignoring case. Digraphs outside the table, such as ('q', 'z'), get
+/// the same low floor value, so this separates common digraphs from the rest rather than
+/// ranking rare ones.
+pub fn english_digraph_logprob(b: Bigram) -> f64 {
+    let b = (b.0.to_ascii_lowercase(), b.1.to_ascii_lowercase());
+
+    DIGRAMS
+        .iter()
+        .find(|(d, _)| *d == b)
+        .map_or(DIGRAM_FLOOR, |(_, percent)| (percent / 100.0).log10())
+}
+
+/// The most common English trigrams, with their approximate frequency as a percentage of all
+/// trigrams in English text.
+const TRIGRAMS: &[(&str, f64)] = &[
+    ("the", 1.81),
+    ("and", 0.73),
+    ("ing", 0.72),
+    ("ent", 0.42),
+    ("ion", 0.42),
+    ("her", 0.36),
+    ("for", 0.34),
+    ("tha", 0.33),
+    ("nth", 0.33),
+    ("int", 0.32),
+    ("ere", 0.31),
+    ("tio", 0.31),
+    ("ter", 0.30),
+    ("est", 0.28),
+    ("ers", 0.28),
+    ("ati", 0.26),
+    ("hat", 0.26),
+    ("ate", 0.25),
+    ("all", 0.25),
+    ("eth", 0.24),
+    ("hes", 0.24),
+    ("ver", 0.24),
+    ("his", 0.24),
+    ("oft", 0.22),
+    ("ith", 0.21),
+    ("fth", 0.21),
+    ("sth", 0.21),
+    ("oth", 0.21),
+    ("res", 0.21),
+    ("ont", 0.20),
+    ("dth", 0.19),
+    ("are", 0.19),
+    ("rea", 0.18),
+    ("ear", 0.18),
+    ("was", 0.18),
+    ("sin", 0.17),
+    ("sto", 0.17),
+    ("tth", 0.17),
+    ("sta", 0.17),
+    ("thi", 0.17),
+    ("tin", 0.16),
+    ("ted", 0.16),
+    ("ons", 0.16),
+    ("edt", 0.15),
+];
+
+/// The log probability given to trigrams that aren't in [TRIGRAMS].
+const TRIGRAM_FLOOR: f64 = -5.0;
+
+/// Score how much a text looks like English, as the average log (base 10) probability of each of
+/// its trigrams according to an embedded table of common English trigrams. Only the letters 'a'
+/// through 'z' are considered, ignoring case. Higher (closer to 0) is more English-like, and
+/// texts with fewer than 3 letters get the lowest possible score.
+///
+/// This is the scoring core of a Playfair breaker, comparing candidate decryptions.
+pub fn english_score(text: &str) -> f64 {
+    let letters: Vec<u8> = text
+        .bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|b| b.to_ascii_lowercase())
+        .collect();
+
+    if letters.len() < 3 {
+        return TRIGRAM_FLOOR;
+    }
+
+    let total: f64 = letters
+        .windows(3)
+        .map(|trigram| {
+            TRIGRAMS
+                .iter()
+                .find(|(t, _)| t.as_bytes() == trigram)
+                .map_or(TRIGRAM_FLOOR, |(_, percent)| (percent / 100.0).log10())
+        })
+        .sum();
+
+    total / (letters.len() - 2) as f64
+}
+
+/// Decrypt a ciphertext under each of the candidate keys, returning the key and decryption that
+/// scores the most English-like under [english_score]. Gives `None` without any candidates.
+pub fn best_decrypt<'a>(ciphertext: &str, candidate_keys: &[&'a str]) -> Option<(&'a str, String)> {
+    candidate_keys
+        .iter()
+        .map(|key| (*key, Playfair::new(key).decrypt(ciphertext)))
+        .max_by(|(_, a), (_, b)| english_score(a).total_cmp(&english_score(b)))
+}
+
+/// Count how many distinct ciphertexts a plaintext encrypts to across a set of keys, showing how
+/// much the choice of key matters for a fixed message. Keys that give the same matrix, such as
+/// "play" and "PLAY", produce the same ciphertext and only count once.
+pub fn ciphertext_spread(plaintext: &str, keys: &[&str]) -> usize {
+    keys.iter()
+        .map(|key| Playfair::new(key).encrypt(plaintext))
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+/// The number of consecutive bigrams two ciphertexts must share for [detect_key_reuse] to flag
+/// them. Shorter runs turn up by chance between unrelated messages.
+pub const KEY_REUSE_MIN_BIGRAMS: usize = 4;
+
+/// Find pairs of ciphertexts that likely share a key and part of their plaintext, giving the
+/// pairs of indices `(i, j)` with `i < j` whose ciphertexts have at least
+/// [KEY_REUSE_MIN_BIGRAMS] consecutive bigrams in common. The same key encrypts the same
+/// bigram the same way every time, so reusing a key for similar messages shows up as repeated
+/// stretches of ciphertext, which is exactly what an attacker looks for.
+pub fn detect_key_reuse(ciphertexts: &[&str]) -> Vec<(usize, usize)> {
+    let bigrams: Vec<Vec<Bigram>> = ciphertexts
+        .iter()
+        .map(|ciphertext| {
+            let letters: Vec<char> = Playfair::normalize(ciphertext).collect();
+            letters.chunks_exact(2).map(|p| (p[0], p[1])).collect()
+        })
+        .collect();
+
+    let mut pairs = Vec::new();
+    for i in 0..bigrams.len() {
+        for j in i + 1..bigrams.len() {
+            if longest_common_run(&bigrams[i], &bigrams[j]) >= KEY_REUSE_MIN_BIGRAMS {
+                pairs.push((i, j));
+            }
+        }
+    }
+
+    pairs
+}
+
+/// The length of the longest run of consecutive bigrams appearing in both sequences.
+fn longest_common_run(a: &[Bigram], b: &[Bigram]) -> usize {
+    // The length of the common run ending at each position of `b`, for the previous bigram of `a`
+    let mut previous = vec![0; b.len() + 1];
+    let mut longest = 0;
+
+    for x in a {
+        let mut current = vec![0; b.len() + 1];
+
+        for (idx, y) in b.iter().enumerate() {
+            if x == y {
+                current[idx + 1] = previous[idx] + 1;
+                longest = longest.max(current[idx + 1]);
+            }
+        }
+
+        previous = current;
+    }
+
+    longest
+}
+
+/// Restore the word boundaries lost by the cipher, inserting spaces into a decrypted letter
+/// stream by greedily taking the longest dictionary word at each point. Letters that don't start
+/// any dictionary word are kept together until the next word is found, so the output always
+/// holds every letter of the input in order.
+///
+/// This is only a heuristic, a greedy match can take a word that cuts into the next one, and the
+/// result is only as good as the dictionary.
+pub fn resegment(text: &str, dict: &HashSet<String>) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let longest = dict.iter().map(|w| w.chars().count()).max().unwrap_or(0);
+
+    let mut words = Vec::new();
+    let mut unknown = String::new();
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        let word = (1..=longest.min(chars.len() - idx))
+            .rev()
+            .map(|len| chars[idx..idx + len].iter().collect::<String>())
+            .find(|candidate| dict.contains(candidate));
+
+        match word {
+            Some(word) => {
+                if !unknown.is_empty() {
+                    words.push(std::mem::take(&mut unknown));
+                }
+                idx += word.chars().count();
+                words.push(word);
+            }
+            None => {
+                unknown.push(chars[idx]);
+                idx += 1;
+            }
+        }
+    }
+
+    if !unknown.is_empty() {
+        words.push(unknown);
+    }
+
+    words.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_letter_frequency() {
+        let counts = letter_frequency("bmodzbxdnabekudmuixmmouvif");
+
+        assert_eq!(counts.iter().sum::<usize>(), 26);
+        assert_eq!(counts[(b'm' - b'a') as usize], 4);
+        assert_eq!(counts[(b'b' - b'a') as usize], 3);
+        assert_eq!(counts[(b'j' - b'a') as usize], 0);
+
+        assert_eq!(letter_frequency("A a!"), letter_frequency("aa"));
+    }
+
+    #[test]
+    fn test_decrypt_variants() {
+        let pf = Playfair::new("playfair example");
+        let variants = decrypt_variants(&pf, "bmodzbxdnabekudmuixmmouvif", 5);
+
+        assert_eq!(variants.len(), 6);
+        assert_eq!(variants[0], pf.decrypt("bmodzbxdnabekudmuixmmouvif"));
+        // Swapping the first two letters only disturbs the first bigram
+        assert_eq!(variants[1], pf.decrypt("mbodzbxdnabekudmuixmmouvif"));
+        assert!(variants[1].ends_with(&variants[0][2..]));
+
+        assert_eq!(decrypt_variants(&pf, "bm", 10).len(), 2);
+    }
+
+    #[test]
+    fn test_fixed_point_bigrams() {
+        assert_eq!(all_bigrams().count(), 625);
+
+        let pf = Playfair::new("playfair example");
+
+        assert!(fixed_point_bigrams(&pf).is_empty());
+    }
+
+    #[test]
+    fn test_unreachable_outputs() {
+        let mut pf = Playfair::new("playfair example");
+        let unreachable = unreachable_outputs(&pf);
+
+        assert_eq!(unreachable.len(), 24);
+        assert!(unreachable.contains(&('a', 'a')));
+        assert!(unreachable.iter().all(|(a, b)| a == b));
+
+        // "xx" is seperated and padded with more 'x's, so gives doubled output
+        let ciphertext: Vec<char> = pf.encrypt("xx").chars().collect();
+        for pair in ciphertext.chunks(2) {
+            assert!(!unreachable.contains(&(pair[0], pair[1])));
+        }
+
+        pf.set_separator_strategy(SeparatorStrategy::ContextDerived);
+        let ciphertext: Vec<char> = pf.encrypt("x").chars().collect();
+        assert!(!unreachable_outputs(&pf).contains(&(ciphertext[0], ciphertext[1])));
+
+        // Neither a seperator nor the padding ever doubles an 'x'
+        pf.set_separator_strategy(SeparatorStrategy::Priority);
+        let unreachable = unreachable_outputs(&pf);
+        assert_eq!(unreachable.len(), 25);
+        let ciphertext: Vec<char> = pf.encrypt("xx").chars().collect();
+        for pair in ciphertext.chunks(2) {
+            assert!(!unreachable.contains(&(pair[0], pair[1])));
+        }
+        pf.set_separator_strategy(SeparatorStrategy::Fixed);
+
+        pf.set_bigram_mode(BigramMode::NoSeparator);
+        assert!(unreachable_outputs(&pf).is_empty());
+    }
+
+    #[test]
+    fn test_keywords_fixing_first_row() {
+        let row = ['m', 'o', 'n', 'a', 'r'];
+        let keywords = keywords_fixing_first_row(row);
+
+        assert_eq!(keywords[0], "monar");
+        assert_eq!(keywords.len(), 21);
+        for kw in &keywords {
+            let matrix = Keyword::new(kw).to_matrix();
+            assert_eq!(std::array::from_fn(|x| matrix[x][0]), row, "{kw}");
+        }
+
+        // Prefixes that the alphabet completes are the shortest keywords
+        let keywords = keywords_fixing_first_row(['z', 'a', 'b', 'c', 'd']);
+        assert_eq!(&keywords[..5], ["z", "za", "zab", "zabc", "zabcd"]);
+
+        assert!(keywords_fixing_first_row(['a', 'b', 'c', 'd', 'a']).is_empty());
+        assert!(keywords_fixing_first_row(['j', 'a', 'b', 'c', 'd']).is_empty());
+    }
+
+    #[test]
+    fn test_english_digraph_logprob() {
+        let th = english_digraph_logprob(('t', 'h'));
+
+        assert!(th > english_digraph_logprob(('e', 'r')));
+        assert!(th > english_digraph_logprob(('q', 'z')));
+        assert_eq!(english_digraph_logprob(('T', 'H')), th);
+        assert_eq!(english_digraph_logprob(('q', 'z')), DIGRAM_FLOOR);
+        assert!(DIGRAMS
+            .iter()
+            .all(|&(d, _)| english_digraph_logprob(d) > DIGRAM_FLOOR));
+    }
+
+    #[test]
+    fn test_unicity_distance() {
+        // "playfirexm" has 10 letters, so there are 25!/15! ≈ 2^44 keywords like it
+        let distance = unicity_distance(&Playfair::new("playfair example"));
+        assert!((13.0..15.0).contains(&distance), "{distance}");
+
+        // Longer keywords make for a larger keyspace, up to all 25! matrices
+        let longest = unicity_distance(&Playfair::new("zyxwvutsrqponmlkihgfedcba"));
+        assert!(longest > distance);
+        assert!((longest - 83.68 / ENGLISH_REDUNDANCY).abs() < 0.01);
+
+        assert_eq!(unicity_distance(&Playfair::new("")), 0.0);
+    }
+
+    #[test]
+    fn test_english_score() {
+        let english = english_score("hide the gold in the tree stump");
+        let ciphertext = english_score("bmodzbxdnabekudmuixmmouvif");
+        let scrambled = english_score("ehdi hte dlog ni teh ertr pmuts");
+
+        assert!(english > ciphertext);
+        assert!(english > scrambled);
+        assert_eq!(english_score("hi"), TRIGRAM_FLOOR);
+    }
+
+    #[test]
+    fn test_best_decrypt() {
+        let ciphertext = Playfair::new("playfair example").encrypt("the cat sat on the mat");
+        let best = best_decrypt(&ciphertext, &["nonsense", "playfair example", "monarchy"]);
+
+        assert_eq!(best.map(|(key, _)| key), Some("playfair example"));
+        assert_eq!(best_decrypt(&ciphertext, &[]), None);
+    }
+
+    #[test]
+    fn test_ciphertext_spread() {
+        let keys = [
+            "playfair example",
+            "monarchy",
+            "keyword",
+            "charles",
+            "Monarchy!",
+        ];
+
+        assert_eq!(
+            ciphertext_spread("hide the gold in the tree stump", &keys),
+            4
+        );
+        assert_eq!(ciphertext_spread("hide the gold", &[]), 0);
+    }
+
+    #[test]
+    fn test_transition_matrix() {
+        let pf = Playfair::new("playfair example");
+        let table = transition_matrix(&pf);
+        let idx = |c| ALPHABET.find(c).unwrap();
+
+        assert_eq!(table[idx('h')][idx('i')], ('b', 'm'));
+        assert_eq!(table[idx('d')][idx('e')], ('o', 'd'));
+        for (a, b) in [('t', 'u'), ('e', 'e'), ('z', 'a')] {
+            assert_eq!(table[idx(a)][idx(b)], pf.map_bigram((a, b)));
+        }
+    }
+
+    #[test]
+    fn test_detect_key_reuse() {
+        let pf = Playfair::new("keyword");
+        let other = Playfair::new("monarchy");
+
+        let messages = [
+            pf.encrypt("meet me at the bridge at noon"),
+            other.encrypt("meet me at the bridge at noon"),
+            pf.encrypt("attack the castle at dawn"),
+            pf.encrypt("meet me at the station tonight"),
+        ];
+        let messages: Vec<&str> = messages.iter().map(String::as_str).collect();
+
+        assert_eq!(detect_key_reuse(&messages), vec![(0, 3)]);
+        assert_eq!(detect_key_reuse(&[]), vec![]);
+    }
+
+    #[test]
+    fn test_key_distance() {
+        let pf = Playfair::new("playfair example");
+
+        assert_eq!(key_distance(&pf, &Playfair::new("Playfair Example")), 0);
+
+        let swapped = Playfair::with_transform("playfair example", |m| {
+            let tmp = m[0][0];
+            m[0][0] = m[3][2];
+            m[3][2] = tmp;
+        });
+        assert_eq!(key_distance(&pf, &swapped), 1);
+        assert_eq!(key_distance(&swapped, &pf), 1);
+
+        // Rotating the rows moves every cell along a cycle of 5
+        let rotated = Playfair::with_transform("playfair example", |m| m.rotate_left(1));
+        assert_eq!(key_distance(&pf, &rotated), 20);
+    }
+
+    #[test]
+    fn test_ciphertext_distance() {
+        let plaintext = "hide the gold in the tree stump";
+        let a = Playfair::new("playfair example").encrypt(plaintext);
+        let b = Playfair::new("playfair examples").encrypt(plaintext);
+        let c = Playfair::new("monarchy").encrypt(plaintext);
+
+        assert_eq!(ciphertext_distance(&a, &a), Some(0));
+        // Adding an 's' to the keyword only moves the letters after it in the matrix
+        assert!(ciphertext_distance(&a, &b).unwrap() < ciphertext_distance(&a, &c).unwrap());
+        assert_eq!(ciphertext_distance(&a, "bmod"), None);
+    }
+
+    #[test]
+    fn test_decrypt_resolve_j() {
+        let pf = Playfair::new("playfair example");
+        let dict: HashSet<String> = ["jump", "the", "jungle", "in", "join", "ioin"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let ciphertext = pf.encrypt("jump");
+        assert_eq!(pf.decrypt(&ciphertext), "iump");
+        assert_eq!(pf.decrypt_resolve_j(&ciphertext, &dict), "jump");
+
+        // "ioin" is a word too, so the 'i' of "join" can't be resolved
+        let ciphertext = pf.encrypt("jump in the jungle join");
+        assert_eq!(
+            pf.decrypt_resolve_j(&ciphertext, &dict),
+            "jumpinthejungleioinx"
+        );
+
+        // Both words match "ixi", the first alphabetically is resolved every time, and a longer
+        // overlapping word takes precedence over both
+        let ciphertext = pf.encrypt("ixi");
+        for _ in 0..8 {
+            let dict: HashSet<String> = ["jxi", "ixj"].into_iter().map(String::from).collect();
+            assert_eq!(pf.decrypt_resolve_j(&ciphertext, &dict), "ixjx");
+
+            let dict: HashSet<String> = ["jxi", "ixj", "jxix"]
+                .into_iter()
+                .map(String::from)
+                .collect();
+            assert_eq!(pf.decrypt_resolve_j(&ciphertext, &dict), "jxix");
+        }
+    }
+
+    #[test]
+    fn test_resegment() {
+        let dict: HashSet<String> = ["hide", "the", "gold", "in", "tree", "stump"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        assert_eq!(resegment("hidethegold", &dict), "hide the gold");
+        assert_eq!(
+            resegment("hidethegoldinthetrexestump", &dict),
+            "hide the gold in the trexe stump"
+        );
+        assert_eq!(resegment("xxhide", &dict), "xx hide");
+        assert_eq!(resegment("", &dict), "");
+    }
+
+    #[test]
+    fn test_index_of_coincidence() {
+        // h, e, w, r, d once, o twice and l three times: (2 + 6) / (10 * 9)
+        let ic = index_of_coincidence("Hello, World!");
+        assert!((ic - 8.0 / 90.0).abs() < 1e-9);
+
+        assert_eq!(index_of_coincidence("a"), 0.0);
+        assert_eq!(index_of_coincidence("abcdef"), 0.0);
+    }
+}
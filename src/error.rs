@@ -0,0 +1,174 @@
+//! Errors that can occur while using the Playfair cipher.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::Position;
+
+/// Errors that can occur while validating a keyword.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeywordError {
+    /// The keyword had no letters in it, so would silently give the plain alphabet matrix.
+    NoLetters,
+    /// The keyword contained a letter that has no place in the matrix.
+    InvalidCharacter(char),
+}
+
+impl fmt::Display for KeywordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeywordError::NoLetters => write!(f, "keyword has no letters"),
+            KeywordError::InvalidCharacter(c) => write!(f, "{c:?} cannot appear in a keyword"),
+        }
+    }
+}
+
+impl Error for KeywordError {}
+
+/// Errors that can occur while validating a [Matrix](crate::Matrix) supplied from outside of a
+/// [Keyword](crate::Keyword).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatrixError {
+    /// The matrix contained a character that isn't one of the 25 letters of the matrix alphabet.
+    InvalidCharacter(char),
+    /// The matrix contained the same letter more than once.
+    DuplicateCharacter(char),
+    /// A cell of a [PartialMatrix](crate::PartialMatrix) at the given position was still empty.
+    EmptyCell(Position),
+    /// The cell at the given position was in a different case from the first cell of the matrix.
+    MixedCase(Position),
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatrixError::InvalidCharacter(c) => write!(f, "{c:?} cannot appear in the matrix"),
+            MatrixError::DuplicateCharacter(c) => write!(f, "{c:?} appears more than once"),
+            MatrixError::EmptyCell((x, y)) => write!(f, "the cell at ({x}, {y}) is empty"),
+            MatrixError::MixedCase((x, y)) => {
+                write!(
+                    f,
+                    "the cell at ({x}, {y}) differs in case from the first cell"
+                )
+            }
+        }
+    }
+}
+
+impl Error for MatrixError {}
+
+/// Errors that can occur while decrypting a ciphertext.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecryptError {
+    /// The ciphertext was too short to contain a checksum.
+    MissingChecksum,
+    /// The checksum carried by the ciphertext did not match the decrypted message, meaning the
+    /// ciphertext was corrupted in transmission or decrypted with the wrong keyword.
+    ChecksumMismatch,
+    /// The ciphertext had an odd number of letters once cleaned up, so a letter was lost or
+    /// added in transmission and it can't be split back into bigrams.
+    OddLength(usize),
+    /// The ciphertext held a character that isn't a letter of the matrix, see
+    /// [DecryptUnknownPolicy::Error](crate::DecryptUnknownPolicy::Error).
+    UnknownCharacter(char),
+}
+
+impl fmt::Display for DecryptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecryptError::MissingChecksum => {
+                write!(f, "ciphertext is too short to hold a checksum")
+            }
+            DecryptError::ChecksumMismatch => {
+                write!(f, "checksum does not match the decrypted message")
+            }
+            DecryptError::OddLength(len) => {
+                write!(f, "ciphertext has an odd number of letters ({len})")
+            }
+            DecryptError::UnknownCharacter(c) => {
+                write!(f, "ciphertext holds {c:?}, which isn't in the matrix")
+            }
+        }
+    }
+}
+
+impl Error for DecryptError {}
+
+/// Errors that can occur while reading a share string produced by
+/// [Playfair::to_share_string](crate::Playfair::to_share_string).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShareError {
+    /// The share string wasn't valid unpadded base64url.
+    InvalidEncoding,
+    /// The share string decoded to the wrong number of bytes.
+    InvalidLength(usize),
+    /// The matrix held by the share string was invalid.
+    InvalidMatrix(MatrixError),
+    /// The share string held an option that isn't recognized.
+    InvalidOption(u8),
+}
+
+impl fmt::Display for ShareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShareError::InvalidEncoding => write!(f, "share string is not valid base64url"),
+            ShareError::InvalidLength(len) => write!(f, "share string holds {len} bytes"),
+            ShareError::InvalidMatrix(e) => write!(f, "share string holds an invalid matrix: {e}"),
+            ShareError::InvalidOption(b) => write!(f, "share string holds an unknown option {b}"),
+        }
+    }
+}
+
+impl Error for ShareError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ShareError::InvalidMatrix(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<MatrixError> for ShareError {
+    fn from(e: MatrixError) -> Self {
+        ShareError::InvalidMatrix(e)
+    }
+}
+
+/// Errors that can occur while combining [KeyRing](crate::KeyRing)s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyRingError {
+    /// Both key rings held different keys under these labels, and the
+    /// [ConflictPolicy](crate::ConflictPolicy) was to error.
+    Conflict(Vec<String>),
+}
+
+impl fmt::Display for KeyRingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyRingError::Conflict(labels) => {
+                write!(f, "conflicting keys for labels: {}", labels.join(", "))
+            }
+        }
+    }
+}
+
+impl Error for KeyRingError {}
+
+/// Errors that can occur while configuring the shift of the cipher, see
+/// [Playfair::set_shift](crate::Playfair::set_shift).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShiftError {
+    /// The shift wasn't between 1 and 4, so would either leave letters in place or wrap around
+    /// to an equivalent shift.
+    OutOfRange(usize),
+}
+
+impl fmt::Display for ShiftError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShiftError::OutOfRange(shift) => write!(f, "shift {shift} is not between 1 and 4"),
+        }
+    }
+}
+
+impl Error for ShiftError {}
@@ -0,0 +1,143 @@
+//! A 7x7 variant of the cipher, whose larger alphabet holds digits and punctuation so that plain
+//! prose survives encryption without losing its spaces and punctuation.
+
+use crate::{Bigram, Cipher, Position, SubstitutionCase};
+
+/// The 49 characters of the 7x7 grid: the letters 'a' to 'z' (including 'j'), the digits, a
+/// dozen punctuation characters including the space, and the [GRID7_FILLER].
+pub const GRID7_ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz0123456789 .,!?'\"-:;()_";
+
+/// The character of the 7x7 grid that separates doubled characters and pads odd length input.
+/// It is never taken from the plaintext, so it can be removed again on decryption, making the
+/// round trip exact.
+pub const GRID7_FILLER: char = '_';
+
+/// A 7x7 grid, indexed the same way as a [Matrix](crate::Matrix).
+pub type Matrix7 = [[char; 7]; 7];
+
+/// Playfair cipher over a 7x7 grid of [GRID7_ALPHABET]. The rules are the same as for the 5x5
+/// matrix, but uppercase letters are lowercased, everything else in the alphabet is kept as-is,
+/// and characters outside of it are dropped. Decryption removes the [GRID7_FILLER], giving back
+/// exactly the normalized plaintext.
+pub struct Playfair7 {
+    /// The grid encryption/decryption is operated over.
+    matrix: Matrix7,
+}
+
+impl Playfair7 {
+    /// Generate a 7x7 cipher from a keyword, filling the grid with the distinct characters of
+    /// the keyword followed by the rest of [GRID7_ALPHABET].
+    pub fn new(kw: &str) -> Self {
+        let mut cells = String::with_capacity(49);
+
+        for c in Playfair7::normalize(kw).chain(GRID7_ALPHABET.chars()) {
+            if !cells.contains(c) {
+                cells.push(c);
+            }
+        }
+
+        let cells: Vec<char> = cells.chars().collect();
+        let matrix = std::array::from_fn(|x| std::array::from_fn(|y| cells[y * 7 + x]));
+
+        Self { matrix }
+    }
+
+    /// Get the grid that encryption/decryption is operated over.
+    pub fn matrix(&self) -> &Matrix7 {
+        &self.matrix
+    }
+
+    /// Get the grid as nested vectors, indexed the same way as [Playfair7::matrix], see also
+    /// [Playfair::matrix_vec](crate::Playfair::matrix_vec).
+    pub fn matrix_vec(&self) -> Vec<Vec<char>> {
+        self.matrix.iter().map(|column| column.to_vec()).collect()
+    }
+
+    /// Normalize an input for the 7x7 grid, lowercasing it and only keeping the characters of
+    /// [GRID7_ALPHABET] other than the [GRID7_FILLER].
+    fn normalize(input: &str) -> impl Iterator<Item = char> + '_ {
+        input
+            .chars()
+            .flat_map(char::to_lowercase)
+            .filter(|&c| c != GRID7_FILLER && GRID7_ALPHABET.contains(c))
+    }
+
+    /// Split normalized input into bigrams, separating doubled characters and padding to an even
+    /// length with the [GRID7_FILLER].
+    fn bigramify(input: &str) -> Vec<Bigram> {
+        let mut chars = Playfair7::normalize(input).peekable();
+        let mut bigrams = Vec::new();
+
+        while let Some(a) = chars.next() {
+            match chars.peek() {
+                Some(&b) if b != a => {
+                    chars.next();
+                    bigrams.push((a, b));
+                }
+                _ => bigrams.push((a, GRID7_FILLER)),
+            }
+        }
+
+        bigrams
+    }
+
+    /// Get the position of a character in the grid.
+    fn position(&self, c: char) -> Position {
+        (0..49)
+            .map(|idx| (idx % 7, idx / 7))
+            .find(|&(x, y)| self.matrix[x][y] == c)
+            .expect("only characters of the grid alphabet are looked up")
+    }
+
+    /// Substitute a bigram, moving `shift` cells along a shared row or column: 1 to encrypt, and
+    /// 6 (one step back, modulo 7) to decrypt. The rectangle rule is its own inverse.
+    fn substitute(&self, (a, b): Bigram, shift: usize) -> Bigram {
+        let (a_pos, b_pos) = (self.position(a), self.position(b));
+
+        let (a_out, b_out) = match SubstitutionCase::of(a_pos, b_pos) {
+            SubstitutionCase::SameColumn => (
+                (a_pos.0, (a_pos.1 + shift) % 7),
+                (b_pos.0, (b_pos.1 + shift) % 7),
+            ),
+            SubstitutionCase::SameRow => (
+                ((a_pos.0 + shift) % 7, a_pos.1),
+                ((b_pos.0 + shift) % 7, b_pos.1),
+            ),
+            SubstitutionCase::Rectangle => ((b_pos.0, a_pos.1), (a_pos.0, b_pos.1)),
+        };
+
+        (self.matrix[a_out.0][a_out.1], self.matrix[b_out.0][b_out.1])
+    }
+}
+
+impl Cipher for Playfair7 {
+    /// Encryption logic for a given plaintext
+    fn encrypt(&self, plaintext: &str) -> String {
+        Playfair7::bigramify(plaintext)
+            .into_iter()
+            .flat_map(|bigram| {
+                let (a, b) = self.substitute(bigram, 1);
+                [a, b]
+            })
+            .collect()
+    }
+
+    /// Decryption logic for a given ciphertext. The ciphertext is lowercased like a plaintext and
+    /// taken in pairs as-is, and the [GRID7_FILLER] is removed from the result.
+    fn decrypt(&self, ciphertext: &str) -> String {
+        let chars: Vec<char> = ciphertext
+            .chars()
+            .flat_map(char::to_lowercase)
+            .filter(|&c| GRID7_ALPHABET.contains(c))
+            .collect();
+
+        chars
+            .chunks(2)
+            .flat_map(|pair| {
+                let (a, b) = self.substitute((pair[0], *pair.get(1).unwrap_or(&GRID7_FILLER)), 6);
+                [a, b]
+            })
+            .filter(|&c| c != GRID7_FILLER)
+            .collect()
+    }
+}
@@ -0,0 +1,95 @@
+//! Collections of configured ciphers, each stored under a label, e.g. one per correspondent.
+
+use std::collections::BTreeMap;
+
+use crate::{KeyRingError, Playfair};
+
+/// What [KeyRing::merge] does when both key rings hold different keys under the same label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Keep the key already in the key ring.
+    Keep,
+    /// Replace the key with the one from the other key ring.
+    Overwrite,
+    /// Fail the merge, leaving the key ring unchanged.
+    Error,
+}
+
+/// A set of ciphers stored under labels, kept in label order.
+#[derive(Default)]
+pub struct KeyRing {
+    /// The ciphers, by label.
+    keys: BTreeMap<String, Playfair>,
+}
+
+impl KeyRing {
+    /// Create an empty key ring.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store a cipher under a label, returning the cipher previously stored under it, if any.
+    pub fn insert(&mut self, label: &str, pf: Playfair) -> Option<Playfair> {
+        self.keys.insert(label.to_string(), pf)
+    }
+
+    /// Get the cipher stored under a label.
+    pub fn get(&self, label: &str) -> Option<&Playfair> {
+        self.keys.get(label)
+    }
+
+    /// Remove and return the cipher stored under a label.
+    pub fn remove(&mut self, label: &str) -> Option<Playfair> {
+        self.keys.remove(label)
+    }
+
+    /// Iterate over the labels in order.
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.keys.keys().map(String::as_str)
+    }
+
+    /// The number of ciphers in the key ring.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Whether the key ring holds no ciphers.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Move every cipher of another key ring into this one, resolving labels present in both
+    /// according to the [ConflictPolicy]. Only labels holding different keys conflict, two
+    /// ciphers with the same matrix and options (compared with [PartialEq]) are the same key.
+    ///
+    /// Gives the conflicting labels in order, or fails with [KeyRingError::Conflict] if there were
+    /// any under [ConflictPolicy::Error], in which case this key ring is left unchanged.
+    pub fn merge(
+        &mut self,
+        other: KeyRing,
+        on_conflict: ConflictPolicy,
+    ) -> Result<Vec<String>, KeyRingError> {
+        let conflicts: Vec<String> = other
+            .keys
+            .iter()
+            .filter(|(label, pf)| {
+                self.keys
+                    .get(*label)
+                    .is_some_and(|existing| existing != *pf)
+            })
+            .map(|(label, _)| label.clone())
+            .collect();
+
+        if on_conflict == ConflictPolicy::Error && !conflicts.is_empty() {
+            return Err(KeyRingError::Conflict(conflicts));
+        }
+
+        for (label, pf) in other.keys {
+            if on_conflict == ConflictPolicy::Overwrite || !self.keys.contains_key(&label) {
+                self.keys.insert(label, pf);
+            }
+        }
+
+        Ok(conflicts)
+    }
+}
@@ -1,5 +1,69 @@
 //! Playfair cipher implementation in Rust
 
+use std::collections::HashSet;
+use std::iter::Peekable;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "analysis")]
+pub mod analysis;
+mod error;
+mod grid;
+mod keyring;
+mod partial;
+mod rekeying;
+mod render;
+mod salted;
+mod share;
+mod stream;
+
+pub use error::{DecryptError, KeyRingError, KeywordError, MatrixError, ShareError, ShiftError};
+pub use grid::{Matrix7, Playfair7, GRID7_ALPHABET, GRID7_FILLER};
+pub use keyring::{ConflictPolicy, KeyRing};
+pub use partial::PartialMatrix;
+pub use rekeying::RekeyingPlayfair;
+pub use render::{AnimationFrame, GridCell, GridLayout};
+pub use salted::SaltedPlayfair;
+
+/// The character inserted between two identical letters that would otherwise share a [Bigram].
+pub const DEFAULT_SEPARATOR: char = 'x';
+
+/// The character appended to an odd length input to complete its final [Bigram].
+pub const DEFAULT_PAD: char = 'x';
+
+/// The fillers [SeparatorStrategy::Priority] chooses from by default, in order of preference.
+pub const DEFAULT_SEPARATOR_PRIORITY: [char; 4] = ['x', 'z', 'q', 'k'];
+
+/// The bigram marking the end of the indicator in [Playfair::encrypt_with_indicator]. The 'q' and
+/// 'z' are rare in English, so an indicator is unlikely to contain them as one of its bigrams.
+pub const INDICATOR_SEPARATOR: Bigram = ('q', 'z');
+
+/// A canonical set of `(keyword, plaintext, ciphertext)` test vectors produced by this crate,
+/// for validating other implementations against. See [Playfair::generate_test_vectors] to
+/// produce more.
+pub const TEST_VECTORS: &[(&str, &str, &str)] = &[
+    (
+        "playfair example",
+        "hide the gold in the tree stump",
+        "bmodzbxdnabekudmuixmmouvif",
+    ),
+    ("playfair", "JaneIsAName", "bpuncnpqfhku"),
+    ("monarchy", "instruments", "gatlmzclrqxa"),
+    (
+        "keyword",
+        "meet me at the bridge at noon",
+        "nkkunkrvvfwdbfgnydumwzes",
+    ),
+    (
+        "charles",
+        "the quick brown fox jumps over the lazy dog",
+        "prbopnagdapvunqvnpitepcgdycscrvzetkv",
+    ),
+];
+
+/// The 25 letters that make up the matrix, in alphabetical order. Since i = j, 'j' is omitted.
+const ALPHABET: &str = "abcdefghiklmnopqrstuvwxyz";
+
 /// Bigram type. Used in the Playfair cipher by grouping characters and performing operations on
 /// those pairs.
 pub type Bigram = (char, char);
@@ -8,8 +72,56 @@ pub type Bigram = (char, char);
 pub type Position = (usize, usize);
 
 /// The Matrix type is a 5 by 5 character array.
+///
+/// The matrix is stored column-major, so `matrix[x][y]` is the letter in column `x` and row `y`,
+/// whereas textbooks write the matrix out row by row. Use [transpose] to convert between the two.
 pub type Matrix = [[char; 5]; 5];
 
+/// Convert a [Matrix] between the column-major layout used by this crate and the row-major layout
+/// used by most references, e.g. to import a matrix written out row by row. Transposing twice
+/// gives back the original matrix.
+pub fn transpose(m: &Matrix) -> Matrix {
+    let mut out = *m;
+
+    for (x, column) in m.iter().enumerate() {
+        for (y, &c) in column.iter().enumerate() {
+            out[y][x] = c;
+        }
+    }
+
+    out
+}
+
+/// Build a [Matrix] from its 25 lowercase letters in fill order (row by row), checking that they
+/// are a permutation of the matrix alphabet. As a `const fn`, a matrix fixed at build time can be
+/// validated by the compiler, with an invalid one failing the build. Outside of a const context,
+/// an invalid matrix panics instead.
+pub const fn const_matrix(cells: [char; 25]) -> Matrix {
+    // A bit for each letter seen so far, which catches duplicates
+    let mut seen = 0u32;
+    let mut matrix = [[' '; 5]; 5];
+    let mut idx = 0;
+
+    while idx < 25 {
+        let c = cells[idx];
+
+        assert!(
+            c.is_ascii_lowercase() && c != 'j',
+            "not a letter of the matrix alphabet"
+        );
+        assert!(
+            seen & 1 << (c as u32 - 'a' as u32) == 0,
+            "duplicate letter in the matrix"
+        );
+
+        seen |= 1 << (c as u32 - 'a' as u32);
+        matrix[idx % 5][idx / 5] = c;
+        idx += 1;
+    }
+
+    matrix
+}
+
 /// Cipher trait, enforces `encrypt` and `decrypt` methods.
 pub trait Cipher {
     /// Encryption logic for a given plaintext
@@ -18,243 +130,2293 @@ pub trait Cipher {
     fn decrypt(&self, ciphertext: &str) -> String;
 }
 
+/// The letter case the [Matrix] is stored in. This is mostly a presentation concern, since
+/// characters are looked up in the matrix case-insensitively, but the ciphertext/plaintext produced
+/// is made up of letters from the matrix and so follows its case.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MatrixCase {
+    /// Store the matrix in lowercase, the default.
+    #[default]
+    Lower,
+    /// Store the matrix in uppercase.
+    Upper,
+}
+
+/// The order the keyword is laid out into the [Matrix] in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MatrixLayout {
+    /// Fill each row left-to-right, top to bottom. This is the default.
+    #[default]
+    Rows,
+    /// Fill the rows in alternating directions ("boustrophedon"), with the first row filled
+    /// left-to-right, the second right-to-left, and so on.
+    Boustrophedon,
+}
+
+impl MatrixLayout {
+    /// Rearrange a matrix filled in [MatrixLayout::Rows] into this layout. Every layout is its
+    /// own inverse, so applying it to a matrix in this layout gives back the rows.
+    fn arrange(self, matrix: &mut Matrix) {
+        match self {
+            MatrixLayout::Rows => {}
+            MatrixLayout::Boustrophedon => {
+                // Reverse every odd row, swapping the x-values from either end
+                for y in (1..5).step_by(2) {
+                    for x in 0..2 {
+                        let tmp = matrix[x][y];
+                        matrix[x][y] = matrix[4 - x][y];
+                        matrix[4 - x][y] = tmp;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// How the rectangle rule picks the replacement letters for a pair of letters on opposite corners
+/// of a rectangle. Implementations differ on this, so the choice aids interop with other tools.
+/// Both conventions are their own inverse, so decryption applies the same rule.
+///
+/// The two conventions pick the same pair of corners, only in the opposite order, so references
+/// that describe the rectangle rule as outputting the letters the other way around are using
+/// [RectangleRule::SwapRows].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RectangleRule {
+    /// Replace each letter with the corner in its own row, i.e. swap the columns of the two
+    /// letters. This is the default, and the convention used by the Wikipedia article. For
+    /// letters `a` and `b` at `(x, y)` positions, the output is `matrix[b.0][a.1]` followed by
+    /// `matrix[a.0][b.1]`.
+    #[default]
+    SwapColumns,
+    /// Replace each letter with the corner in its own column, i.e. swap the rows of the two
+    /// letters. The output is `matrix[a.0][b.1]` followed by `matrix[b.0][a.1]`, the reverse of
+    /// [RectangleRule::SwapColumns].
+    SwapRows,
+}
+
+/// Hash bytes with 64-bit FNV-1a, a simple and stable non-cryptographic hash.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// The Levenshtein distance between two strings, the number of single character insertions,
+/// deletions and substitutions needed to turn one into the other.
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    // Only the previous row of the distance table is needed to compute the next
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// The rule of the Playfair cipher that applies to a [Bigram], based on where its letters are in
+/// the [Matrix].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubstitutionCase {
+    /// Both letters are in the same column, so they are replaced by the letters below them.
+    SameColumn,
+    /// Both letters are in the same row, so they are replaced by the letters to their right.
+    SameRow,
+    /// The letters form the corners of a rectangle, so they are replaced by the letters on the
+    /// opposite corners of their row.
+    Rectangle,
+}
+
+impl SubstitutionCase {
+    /// Determine which case applies to the letters at the given positions.
+    fn of(a_pos: Position, b_pos: Position) -> Self {
+        if a_pos.0 == b_pos.0 {
+            SubstitutionCase::SameColumn
+        } else if a_pos.1 == b_pos.1 {
+            SubstitutionCase::SameRow
+        } else {
+            SubstitutionCase::Rectangle
+        }
+    }
+}
+
+/// A single step of an encryption, describing how one [Bigram] was enciphered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BigramStep {
+    /// The plaintext bigram.
+    pub input: Bigram,
+    /// The positions of the plaintext letters in the matrix.
+    pub input_positions: (Position, Position),
+    /// The rule that was applied.
+    pub case: SubstitutionCase,
+    /// The ciphertext bigram.
+    pub output: Bigram,
+    /// The positions of the ciphertext letters in the matrix.
+    pub output_positions: (Position, Position),
+}
+
+/// An event emitted for each [Bigram] encrypted by [Playfair::encrypt_with_observer].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BigramEvent {
+    /// The plaintext bigram.
+    pub input: Bigram,
+    /// The rule that was applied.
+    pub case: SubstitutionCase,
+    /// The ciphertext bigram.
+    pub output: Bigram,
+}
+
+/// Presentation options for [Playfair::encrypt_formatted].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Whether the ciphertext is written in uppercase.
+    pub uppercase: bool,
+    /// The number of letters in each group, or `None` to not group the ciphertext at all.
+    pub group_size: Option<usize>,
+    /// The character put between groups. This shouldn't be a letter, otherwise it would be
+    /// taken as part of the ciphertext when decrypting.
+    pub group_sep: char,
+}
+
+impl Default for FormatOptions {
+    /// Lowercase and ungrouped, the same as [Cipher::encrypt], with groups separated by spaces
+    /// once a group size is set.
+    fn default() -> Self {
+        Self {
+            uppercase: false,
+            group_size: None,
+            group_sep: ' ',
+        }
+    }
+}
+
+/// The result of comparing a received ciphertext with the one expected for a known plaintext, see
+/// [Playfair::verify_transmission].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransmissionReport {
+    /// The ciphertext the plaintext encrypts to.
+    pub expected: String,
+    /// The letters of the received ciphertext, normalized.
+    pub received: String,
+    /// The positions (counted in letters) at which the received ciphertext differs from the
+    /// expected one, including every position only one of them reaches.
+    pub mismatches: Vec<usize>,
+}
+
+impl TransmissionReport {
+    /// Whether the ciphertext arrived without any corruption.
+    pub fn is_intact(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// The letters a letter moves to under the same row and same column rules, see
+/// [Playfair::neighbors].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Neighbors {
+    /// The letter to the right, wrapping around to the start of the row, which the letter
+    /// encrypts to when paired with another letter in its row.
+    pub right: char,
+    /// The letter below, wrapping around to the top of the column, which the letter encrypts to
+    /// when paired with another letter in its column.
+    pub below: char,
+}
+
+/// Statistics about a single encryption, see [Playfair::encrypt_stats].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptStats {
+    /// The number of characters in the plaintext.
+    pub input_len: usize,
+    /// The number of plaintext characters that were dropped, such as spaces and punctuation.
+    pub dropped: usize,
+    /// The number of seperators and padding letters inserted.
+    pub fillers_inserted: usize,
+    /// The number of characters in the ciphertext.
+    pub output_len: usize,
+    /// The ciphertext itself.
+    pub ciphertext: String,
+}
+
+/// The direction the rest of the alphabet is filled in after the letters of a [Keyword].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillOrder {
+    /// Pad with the alphabet in order, "abcde...". This is the traditional fill.
+    #[default]
+    Forward,
+    /// Pad with the alphabet in reverse, "zyxwv...".
+    Reverse,
+}
+
 /// Keyword structure, used in constructing the matrix in which the encryption is performed over.
 #[derive(Debug, PartialEq)]
 pub struct Keyword(String);
 
 impl Keyword {
+    /// The length of a run of alphabetically consecutive letters in a keyword at which
+    /// [Keyword::is_mostly_ordered] considers the keyword weak. With 8 or more letters in a row
+    /// left in alphabetical order, close to a third of the matrix is the same as it would be for
+    /// any other short keyword.
+    pub const ORDERED_RUN_THRESHOLD: usize = 8;
+
     /// Create a keyword from an initial input. This will have a size of 25 and will not have any
     /// duplicate letters, and equate the letter 'i' to the letter 'j'. This is to conform to the 5x5 matrix that
     /// the Playfair cipher is used on. The letter 'j' was chosen arbitrarily due to its low use in
     /// the English language. This will mean that upon decryption, you'll notice anything that once
     /// was a 'j' in the initial plain text is now an 'i'.
     ///
-    /// # Example
-    /// Key: `playfair`
-    /// Message: `Jane`
-    /// Encrypted message: `bpun`
-    /// Decrypted of encryption: `iane`
+    /// # Example
+    /// Key: `playfair`
+    /// Message: `Jane`
+    /// Encrypted message: `bpun`
+    /// Decrypted of encryption: `iane`
+    ///
+    /// Two things to note with this, it turns everything lowercase for easier searching and
+    /// complexity, and j's are now converted to i's.
+    ///
+    /// ## TODO
+    /// According to
+    /// [this](https://users.rust-lang.org/t/fast-removing-chars-from-string/24554) post, using
+    /// `.retain()` on the initial filitering we do may be faster in release builds. Investigate
+    /// more.
+    pub fn new(initial: &str) -> Self {
+        Keyword::with_fill_order(initial, FillOrder::Forward)
+    }
+
+    /// Create a keyword like [Keyword::new], padding the letters of the input with the rest of
+    /// the alphabet in the given [FillOrder].
+    pub fn with_fill_order(initial: &str, order: FillOrder) -> Self {
+        // Create a string with the capacity of 25 since we know how big this will be. This will
+        // eliminate the need for a reallocation, if Rust defaults the capacity to less than 25.
+        let mut buffer = String::with_capacity(25);
+
+        // Ensure we only take the alphabetic parts of the input string and
+        // remove any instance of 'j'.
+        let mut parsed: String = Playfair::normalize(initial).filter(|c| *c != 'j').collect();
+
+        // Append the alphabet (equating 'i' = 'j', thus omitting 'j') to the initial input, to fill in the rest of the possible letters
+        // that the initial input might not cover.
+        match order {
+            FillOrder::Forward => parsed.push_str(ALPHABET),
+            FillOrder::Reverse => parsed.extend(ALPHABET.chars().rev()),
+        }
+
+        // We only need 25 letters, so keep pushing to the buffer while we have less than 25
+        // characters.
+        while buffer.len() < 25 {
+            // Loop over each character in the input and alphabet string, checking that the
+            // character is alphabetic since we can't use numbers of symbols in our Matrix.
+            for c in parsed.chars() {
+                // Check that the character does not exist in the buffer
+                if buffer.find(c).is_none() {
+                    // If so, push to the buffer
+                    buffer.push(c);
+                }
+            }
+        }
+
+        // Return the generated keyword
+        Self(buffer)
+    }
+
+    /// Create a keyword like [Keyword::new], but fill the cells after the letters of the input
+    /// with a permutation of the rest of the alphabet chosen by `seed`, rather than leaving them
+    /// in alphabetical order. This removes the long ordered run that weakens short keywords (see
+    /// [Keyword::is_mostly_ordered]). The recipient needs both the input and the seed.
+    ///
+    /// The permutation comes from [rand::rngs::StdRng], whose output may change between
+    /// versions of `rand`, so both parties should use the same version of this crate.
+    #[cfg(feature = "rand")]
+    pub fn new_seeded(initial: &str, seed: u64) -> Self {
+        use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+        let Keyword(letters) = Keyword::new(initial);
+
+        // The number of distinct letters taken from the input, which stay where they are
+        let mut distinct = String::new();
+        for c in Playfair::normalize(initial).filter(|c| *c != 'j') {
+            if !distinct.contains(c) {
+                distinct.push(c);
+            }
+        }
+
+        let (fixed, rest) = letters.split_at(distinct.len());
+        let mut rest: Vec<char> = rest.chars().collect();
+        rest.shuffle(&mut StdRng::seed_from_u64(seed));
+
+        Self(fixed.chars().chain(rest).collect())
+    }
+
+    /// Create a keyword from a numeric PIN, for users who would rather remember digits. Starting
+    /// before 'a', each digit `d` moves `d + 1` letters along the alphabet (wrapping around), and
+    /// the letters landed on make up the input to [Keyword::new]. Anything but digits is ignored.
+    ///
+    /// A PIN makes a weak key: a 4 digit PIN has only 10,000 possible keys, which can all be
+    /// tried in moments, and its few letters leave most of the matrix in alphabetical order.
+    pub fn from_pin(pin: &str) -> Self {
+        let letters: Vec<char> = ALPHABET.chars().collect();
+        let mut idx = letters.len() - 1;
+
+        let initial: String = pin
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .map(|d| {
+                idx = (idx + d as usize + 1) % letters.len();
+                letters[idx]
+            })
+            .collect();
+
+        Keyword::new(&initial)
+    }
+
+    /// Create a keyword like [Keyword::new], but reject inputs that would silently give a
+    /// useless or broken matrix: those without any letters (such as "" or "123"), which would
+    /// give the plain alphabet, and those with letters outside of 'a' to 'z'.
+    pub fn try_new(initial: &str) -> Result<Self, KeywordError> {
+        let mut letters = Playfair::normalize(initial).peekable();
+
+        if letters.peek().is_none() {
+            return Err(KeywordError::NoLetters);
+        }
+        if let Some(c) = letters.find(|c| !c.is_ascii_lowercase()) {
+            return Err(KeywordError::InvalidCharacter(c));
+        }
+
+        Ok(Keyword::new(initial))
+    }
+
+    /// Create a keyword from a multi-word key phrase, e.g. `["secret", "agent", "nine"]`. This
+    /// gives the same keyword as [Keyword::new] on the words joined together, but each word is
+    /// validated like [Keyword::try_new]. Along with the keyword, the indices of the words that
+    /// contributed nothing to it (because all of their letters were already seen in earlier
+    /// words) are returned, since such words don't strengthen the key.
+    pub fn from_words(words: &[&str]) -> Result<(Self, Vec<usize>), KeywordError> {
+        let mut seen = String::new();
+        let mut redundant = Vec::new();
+
+        for (idx, word) in words.iter().enumerate() {
+            Keyword::try_new(word)?;
+
+            // 'j' shares a cell with 'i', so counts as the same letter
+            let before = seen.len();
+            for c in Playfair::normalize(word).map(|c| if c == 'j' { 'i' } else { c }) {
+                if !seen.contains(c) {
+                    seen.push(c);
+                }
+            }
+
+            if seen.len() == before {
+                redundant.push(idx);
+            }
+        }
+
+        Ok((Keyword::new(&words.concat()), redundant))
+    }
+
+    /// Check whether the keyword leaves a long stretch of the alphabet in order, which happens
+    /// when the keyword is short, signaling a weak key. This is true when the keyword contains a
+    /// run of at least [Keyword::ORDERED_RUN_THRESHOLD] alphabetically consecutive letters (with
+    /// 'i' being followed by 'k').
+    pub fn is_mostly_ordered(&self) -> bool {
+        self.longest_ordered_run() >= Keyword::ORDERED_RUN_THRESHOLD
+    }
+
+    /// Suggest how many distinct letters a keyword should contain so that the longest run of
+    /// alphabetically consecutive letters left in its matrix (see [Keyword::is_mostly_ordered])
+    /// is at most `target_ordered_run`.
+    ///
+    /// This is only a heuristic. It assumes the keyword's letters are spread randomly through
+    /// the alphabet, in which case removing `n` of them leaves `n + 1` runs with the longest
+    /// expected to be around `(25 - n) / (n + 1) * H(n + 1)` letters, `H` being the harmonic
+    /// number. A keyword whose own letters are clustered (or in alphabetical order) will need
+    /// more letters than suggested.
+    pub fn suggest_min_length(target_ordered_run: usize) -> usize {
+        let mut harmonic = 1.0;
+
+        for n in 0..25 {
+            let expected_run = (25 - n) as f64 / (n + 1) as f64 * harmonic;
+
+            if expected_run <= target_ordered_run as f64 {
+                return n;
+            }
+
+            harmonic += 1.0 / (n + 2) as f64;
+        }
+
+        // With every letter in the keyword, nothing is left in order.
+        25
+    }
+
+    /// Get the length of the longest run of alphabetically consecutive letters in the keyword.
+    fn longest_ordered_run(&self) -> usize {
+        let mut longest = 0;
+        let mut current = 0;
+        let mut previous = None;
+
+        for idx in self.0.chars().filter_map(|c| ALPHABET.find(c)) {
+            // Extend the run if this letter directly follows the last, otherwise start a new one
+            current = if previous.map(|p| p + 1) == Some(idx) {
+                current + 1
+            } else {
+                1
+            };
+            longest = longest.max(current);
+            previous = Some(idx);
+        }
+
+        longest
+    }
+
+    /// Find the shortest keyword that generates the given matrix, if there is one. This is the
+    /// part of the matrix before the rest of the alphabet follows in order, and is often much
+    /// shorter (and easier to share) than the full 25 letters. Gives `None` if the matrix isn't
+    /// a valid arrangement of the matrix alphabet.
+    pub fn minimal_for(matrix: &Matrix) -> Option<String> {
+        let cells: [char; 25] = std::array::from_fn(|idx| matrix[idx % 5][idx / 5]);
+        let full = Keyword::from_cells(cells).ok()?.0;
+
+        // Find the shortest prefix after which the unused letters follow in alphabetical order
+        (0..=25)
+            .map(|len| &full[..len])
+            .find(|prefix| {
+                ALPHABET
+                    .chars()
+                    .filter(|c| !prefix.contains(*c))
+                    .eq(full[prefix.len()..].chars())
+            })
+            .map(str::to_string)
+    }
+
+    /// Create a keyword directly from the 25 cells of a matrix in fill order, validating that
+    /// each of the letters of the matrix alphabet appears exactly once. Letters are accepted in
+    /// either case.
+    fn from_cells(cells: [char; 25]) -> Result<Self, MatrixError> {
+        let mut buffer = String::with_capacity(25);
+
+        for c in cells {
+            let c = c.to_ascii_lowercase();
+
+            if !c.is_ascii_lowercase() || c == 'j' {
+                return Err(MatrixError::InvalidCharacter(c));
+            }
+            if buffer.contains(c) {
+                return Err(MatrixError::DuplicateCharacter(c));
+            }
+
+            buffer.push(c);
+        }
+
+        // 25 distinct letters from a 25 letter alphabet, so every letter is accounted for
+        Ok(Self(buffer))
+    }
+
+    /// Convert the keyword into a 5x5 [Matrix] array type in.
+    /// TODO: This can be converted to a 1-d array
+    pub fn to_matrix(&self) -> Matrix {
+        self.to_matrix_with_case(MatrixCase::Lower)
+    }
+
+    /// Convert the keyword into a 5x5 [Matrix], storing the letters in the given [MatrixCase].
+    pub fn to_matrix_with_case(&self, case: MatrixCase) -> Matrix {
+        // Initialize a matrix to null-bytes to start. They will all be overwritten
+        let mut mtx: Matrix = [['\0'; 5]; 5];
+
+        for (idx, chr) in self.0.char_indices() {
+            // Perform the x-value calcuation by using modular arithmetic
+            let x = idx % 5;
+            // Perform the y-value calculation by using integer division
+            let y = idx / 5;
+
+            // Set the char at the given x, y value
+            mtx[x][y] = match case {
+                MatrixCase::Lower => chr,
+                MatrixCase::Upper => chr.to_ascii_uppercase(),
+            };
+        }
+
+        // Return the matrix
+        mtx
+    }
+}
+
+/// Playfair cipher structure, stores data needed during the encryption/decryption
+pub struct Playfair {
+    /// The keyword in which we generate the matrix from.
+    keyword: Keyword,
+    /// The matrix which encryption/decryption is operated over. This is built lazily from the
+    /// keyword the first time it is needed.
+    matrix: OnceLock<Arc<Matrix>>,
+    /// The letter case the matrix is stored in.
+    case: MatrixCase,
+    /// The convention used for the rectangle rule.
+    rectangle_rule: RectangleRule,
+    /// The order the keyword is laid out into the matrix in.
+    layout: MatrixLayout,
+    /// How far the same row and same column rules move letters, from 1 to 4.
+    shift: usize,
+    /// How the input is grouped into bigrams.
+    bigram_mode: BigramMode,
+    /// What happens to digits in the input.
+    digit_policy: DigitPolicy,
+    /// How seperators between doubled letters are chosen.
+    separator_strategy: SeparatorStrategy,
+    /// The fillers to choose from under [SeparatorStrategy::Priority], in order of preference.
+    separator_priority: Vec<char>,
+    /// A custom normalization of plaintext characters, replacing the default one.
+    fold: Option<fn(char) -> Option<char>>,
+    /// Whether the matrix is the one generated from the keyword. This is no longer the case once
+    /// the matrix has been transformed with [Playfair::with_transform].
+    canonical: bool,
+}
+
+impl PartialEq for Playfair {
+    /// Two ciphers are equal when they encrypt and decrypt identically: they have the same matrix
+    /// (in the same case and layout) and every option is the same. How the matrix was arrived
+    /// at, e.g. from which keyword, doesn't matter.
+    fn eq(&self, other: &Self) -> bool {
+        self.matrix() == other.matrix()
+            && self.rectangle_rule == other.rectangle_rule
+            && self.shift == other.shift
+            && self.bigram_mode == other.bigram_mode
+            && self.digit_policy == other.digit_policy
+            && self.separator_strategy == other.separator_strategy
+            && self.separator_priority == other.separator_priority
+            // Functions are compared by address, the same function can't be detected reliably
+            && self.fold.map(|f| f as usize) == other.fold.map(|f| f as usize)
+    }
+}
+
+impl Cipher for Playfair {
+    /// Encryption logic for a given plaintext
+    fn encrypt(&self, plaintext: &str) -> String {
+        self.encrypt_chars(plaintext).collect()
+    }
+
+    /// Decryption logic for a given ciphertext
+    fn decrypt(&self, ciphertext: &str) -> String {
+        // The ciphertext is made of matrix letters, so isn't folded like a plaintext, and its
+        // letters are paired as they are, since a padded ('x', 'x') encrypts to a doubled bigram
+        let bigrams = Bigrams {
+            chars: Playfair::normalize(ciphertext).peekable(),
+            mode: BigramMode::NoSeparator,
+            context: None,
+            priority: None,
+        };
+
+        bigrams
+            .flat_map(|bigram| {
+                let (a, b) = self.decrypt_bigram(bigram);
+                [a, b]
+            })
+            .collect()
+    }
+}
+
+/// A lightweight, read-only view of a [Playfair] cipher implementing [Cipher], for APIs that want
+/// to accept something implementing the trait without taking ownership of the cipher. Views are
+/// `Copy`, and since a [Playfair] is `Sync` any number of them can encrypt concurrently.
+#[derive(Clone, Copy)]
+pub struct PlayfairView<'a>(&'a Playfair);
+
+impl Cipher for PlayfairView<'_> {
+    /// Encryption logic for a given plaintext
+    fn encrypt(&self, plaintext: &str) -> String {
+        self.0.encrypt(plaintext)
+    }
+
+    /// Decryption logic for a given ciphertext
+    fn decrypt(&self, ciphertext: &str) -> String {
+        self.0.decrypt(ciphertext)
+    }
+}
+
+impl<'a> From<&'a Playfair> for PlayfairView<'a> {
+    /// Borrow a cipher as a view.
+    fn from(pf: &'a Playfair) -> Self {
+        PlayfairView(pf)
+    }
+}
+
+/// What [Playfair::decrypt_with_policy] does with characters in a ciphertext that aren't letters
+/// of the matrix (including 'j'), such as digits or punctuation. Whitespace is always ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DecryptUnknownPolicy {
+    /// Decrypt exactly like [Cipher::decrypt], which drops everything but letters and treats 'j'
+    /// as 'i'.
+    #[default]
+    Drop,
+    /// Copy the characters into the plaintext at the same place among the letters, so that e.g.
+    /// punctuation survives.
+    PassThrough,
+    /// Fail with [DecryptError::UnknownCharacter], for ciphertext that is expected to be clean.
+    Error,
+}
+
+/// What happens to digits in the input, which have no place in the matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DigitPolicy {
+    /// Digits are dropped along with every other character that isn't a letter.
+    #[default]
+    Drop,
+    /// Each digit is spelled out as its English name before encryption, so "42" is encrypted as
+    /// "fourtwo". This is lossy, decryption gives back the spelled out words rather than the
+    /// digits and it is up to the reader to recognize them, but the numbers at least survive.
+    SpellOut,
+}
+
+/// The English names of the digits 0 to 9, used by [DigitPolicy::SpellOut].
+const DIGIT_NAMES: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// How normalized input is grouped into [Bigram]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BigramMode {
+    /// A letter followed by a duplicate of itself is paired with the [DEFAULT_SEPARATOR], and the
+    /// duplicate starts the next bigram. This is the traditional grouping.
+    #[default]
+    Standard,
+    /// Letters are paired as they come, so doubled letters stay together in one bigram, and only
+    /// a trailing odd letter is padded with the [DEFAULT_PAD]. This is simpler to explain, which
+    /// makes it handy for teaching, but it is **not** the Playfair cipher as usually described.
+    ///
+    /// A doubled plaintext letter shares a position in the matrix, so it always encrypts to a
+    /// doubled ciphertext letter, leaking exactly the structure the seperator exists to hide.
+    /// The ciphertext also has to be decrypted in this mode, the standard grouping would split
+    /// the doubled ciphertext letters apart and garble the message.
+    NoSeparator,
+}
+
+/// How the seperator put between doubled letters is chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SeparatorStrategy {
+    /// Always use the [DEFAULT_SEPARATOR].
+    #[default]
+    Fixed,
+    /// Use the letter diagonally below and to the right of the doubled letter in the matrix
+    /// (wrapping around the edges), so the seperator varies with the letter rather than always
+    /// being the same, easily spotted, 'x'. It is still deterministic, so
+    /// [Playfair::decrypt_clean] recomputes it to remove the seperators again.
+    ContextDerived,
+    /// Use the first letter of the priority list (see [Playfair::set_separator_priority]) that
+    /// differs from the doubled letter, so a seperator never forms a doubled bigram. This avoids
+    /// e.g. "xx" being seperated by another 'x'. The padding is the [DEFAULT_PAD], unless the
+    /// last letter is the pad letter itself, in which case it is chosen from the list the same
+    /// way, so no filler ever forms a doubled bigram.
+    Priority,
+}
+
+/// The seperator [SeparatorStrategy::ContextDerived] puts after a doubled letter: the letter
+/// diagonally below and to the right of it in the matrix, wrapping around the edges.
+fn context_separator(matrix: &Matrix, c: char) -> char {
+    let c = if c.eq_ignore_ascii_case(&'j') { 'i' } else { c };
+
+    for (x, column) in matrix.iter().enumerate() {
+        if let Some(y) = column.iter().position(|m| m.eq_ignore_ascii_case(&c)) {
+            return matrix[(x + 1) % 5][(y + 1) % 5].to_ascii_lowercase();
+        }
+    }
+
+    DEFAULT_SEPARATOR
+}
+
+/// The filler [SeparatorStrategy::Priority] puts after a letter: the first letter of the
+/// priority list that differs from it, falling back on the [DEFAULT_SEPARATOR_PRIORITY] in case
+/// the list has no such letter.
+fn priority_separator(priority: &[char], c: char) -> char {
+    priority
+        .iter()
+        .chain(&DEFAULT_SEPARATOR_PRIORITY)
+        .copied()
+        .find(|p| !p.eq_ignore_ascii_case(&c))
+        .expect("the default priority list has distinct letters")
+}
+
+/// The padding [SeparatorStrategy::Priority] puts after a trailing letter: the [DEFAULT_PAD],
+/// or the [priority_separator] for a trailing pad letter so the two don't form a doubled bigram.
+fn priority_pad(priority: &[char], c: char) -> char {
+    if c.eq_ignore_ascii_case(&DEFAULT_PAD) {
+        priority_separator(priority, c)
+    } else {
+        DEFAULT_PAD
+    }
+}
+
+/// Lazily groups normalized input into [Bigram]s. A letter that is followed by a duplicate of
+/// itself is paired with a seperator instead (unless using [BigramMode::NoSeparator]), and a
+/// letter followed by nothing at all is paired with the [DEFAULT_PAD] (or the [priority_pad]).
+struct Bigrams<I: Iterator<Item = char>> {
+    /// The normalized characters that are still to be paired up.
+    chars: Peekable<I>,
+    /// How doubled letters are grouped.
+    mode: BigramMode,
+    /// The matrix to derive seperators from under [SeparatorStrategy::ContextDerived], otherwise
+    /// the [DEFAULT_SEPARATOR] is used.
+    context: Option<Matrix>,
+    /// The fillers to choose seperators and the padding from under
+    /// [SeparatorStrategy::Priority].
+    priority: Option<Vec<char>>,
+}
+
+impl<I: Iterator<Item = char>> Iterator for Bigrams<I> {
+    type Item = Bigram;
+
+    /// Produce the next bigram, consuming either one or two characters of the input.
+    fn next(&mut self) -> Option<Bigram> {
+        let a = self.chars.next()?;
+
+        match self.chars.peek() {
+            // The next character differs, so it completes this bigram.
+            Some(&b) if b != a || self.mode == BigramMode::NoSeparator => {
+                self.chars.next();
+                Some((a, b))
+            }
+            // The next character duplicates this one, so a seperator goes between them and the
+            // duplicate starts the next bigram.
+            Some(_) => match (&self.context, &self.priority) {
+                (Some(matrix), _) => Some((a, context_separator(matrix, a))),
+                (_, Some(priority)) => Some((a, priority_separator(priority, a))),
+                _ => Some((a, DEFAULT_SEPARATOR)),
+            },
+            // We ran out of characters and need to pad to an even length.
+            None => match &self.priority {
+                Some(priority) => Some((a, priority_pad(priority, a))),
+                None => Some((a, DEFAULT_PAD)),
+            },
+        }
+    }
+}
+
+impl Playfair {
+    /// Generates a new Playfair cipher structure with the keyword and appropriate alphabet padding to
+    /// ensure it can fit into the matrix.
+    pub fn new(kw: &str) -> Self {
+        // Generate the keyword from the given input
+        Playfair::from_keyword(Keyword::new(kw))
+    }
+
+    /// Generate a Playfair cipher from a keyword like [Playfair::new], padding the keyword with
+    /// the rest of the alphabet in the given [FillOrder].
+    pub fn with_fill_order(kw: &str, order: FillOrder) -> Self {
+        Playfair::from_keyword(Keyword::with_fill_order(kw, order))
+    }
+
+    /// Generate a Playfair cipher that normalizes plaintext with a custom fold, rather than the
+    /// default lowercasing and dropping of anything that isn't a letter. The fold maps each
+    /// character of the input onto a letter of the matrix alphabet, or to `None` to drop it,
+    /// which puts the 'i'/'j' merge, case folding, and any transliteration under the caller's
+    /// control. The keyword is folded the same way.
+    ///
+    /// The fold should only produce lowercase letters of the matrix alphabet (anything else is
+    /// looked up as 'i'), and should leave the letters it produces unchanged, so that decrypted
+    /// text folds to itself. The [DigitPolicy] doesn't apply, since the fold decides what
+    /// happens to digits.
+    pub fn with_fold(kw: &str, fold: fn(char) -> Option<char>) -> Self {
+        let keyword: String = kw.chars().filter_map(fold).collect();
+        let mut pf = Playfair::new(&keyword);
+        pf.fold = Some(fold);
+
+        pf
+    }
+
+    /// Construct a Playfair cipher from an already generated keyword. The matrix is only
+    /// constructed once it is first needed.
+    fn from_keyword(keyword: Keyword) -> Self {
+        Self {
+            keyword,
+            matrix: OnceLock::new(),
+            case: MatrixCase::default(),
+            rectangle_rule: RectangleRule::default(),
+            layout: MatrixLayout::default(),
+            shift: 1,
+            bigram_mode: BigramMode::default(),
+            digit_policy: DigitPolicy::default(),
+            separator_strategy: SeparatorStrategy::default(),
+            separator_priority: DEFAULT_SEPARATOR_PRIORITY.to_vec(),
+            fold: None,
+            canonical: true,
+        }
+    }
+
+    /// Generate a Playfair cipher from a keyword like [Playfair::new], then apply a
+    /// transformation to its matrix, e.g. reversing the rows or rotating it. This allows
+    /// experimenting with perturbations of the matrix without a new constructor for each idea.
+    ///
+    /// The transformation should only permute the matrix, otherwise encryption may not be
+    /// reversible. Since the matrix no longer follows from the keyword, the keyword is marked as
+    /// no longer canonical (see [Playfair::is_keyword_canonical]).
+    pub fn with_transform<F: Fn(&mut Matrix)>(kw: &str, f: F) -> Self {
+        let mut pf = Playfair::new(kw);
+        let mut matrix = *pf.matrix();
+
+        f(&mut matrix);
+
+        pf.matrix = OnceLock::from(Arc::new(matrix));
+        pf.canonical = false;
+        pf
+    }
+
+    /// Generate a Playfair cipher from a keyword like [Playfair::new], then reorder the columns of
+    /// its matrix by a second key, as in a columnar transposition: the columns are read out in
+    /// the alphabetical order of the first 5 letters of [Keyword::new] for `col_key`. E.g. for
+    /// "cab" the letters are "cabde", so the second column (under the 'a') comes first.
+    ///
+    /// Decryption uses the same reordered matrix, so needs no separate inverse. Column keys whose
+    /// first 5 letters are already in alphabetical order, such as "", leave the matrix as it is.
+    /// Like [Playfair::with_transform], the keyword is marked as no longer canonical.
+    pub fn with_column_key(kw: &str, col_key: &str) -> Self {
+        let Keyword(letters) = Keyword::new(col_key);
+        let mut order: Vec<(char, usize)> = letters.chars().take(5).zip(0..).collect();
+        order.sort_unstable();
+
+        Playfair::with_transform(kw, |matrix| {
+            let original = *matrix;
+            for (column, &(_, from)) in order.iter().enumerate() {
+                matrix[column] = original[from];
+            }
+        })
+    }
+
+    /// Check whether the matrix is the one generated from the keyword, which is no longer true
+    /// once it has been transformed with [Playfair::with_transform].
+    pub fn is_keyword_canonical(&self) -> bool {
+        self.canonical
+    }
+
+    /// Check that the matrix actually is the one the keyword generates, with the configured
+    /// [MatrixCase] and [MatrixLayout]. Unlike [Playfair::is_keyword_canonical], which records
+    /// whether the matrix was deliberately transformed, this compares the two, guarding against
+    /// bugs where only one of them was updated.
+    pub fn is_consistent(&self) -> bool {
+        let mut expected = self.keyword.to_matrix_with_case(self.case);
+        self.layout.arrange(&mut expected);
+
+        *self.matrix() == expected
+    }
+
+    /// Pack the matrix into 25 ASCII bytes in fill order, a compact form for storing or
+    /// transmitting a configured cipher. See [Playfair::from_bytes] to restore it.
+    pub fn to_bytes(&self) -> [u8; 25] {
+        let matrix = self.matrix();
+
+        std::array::from_fn(|idx| matrix[idx % 5][idx / 5] as u8)
+    }
+
+    /// Restore a cipher packed by [Playfair::to_bytes], validating that the bytes hold each
+    /// letter of the matrix alphabet exactly once.
+    pub fn from_bytes(bytes: &[u8; 25]) -> Result<Playfair, MatrixError> {
+        let keyword = Keyword::from_cells(bytes.map(char::from))?;
+
+        Ok(Playfair::from_keyword(keyword))
+    }
+
+    /// Construct a cipher from a raw matrix, for a receiver that was handed the matrix rather
+    /// than the keyword. The matrix is validated to hold each letter of the matrix alphabet
+    /// exactly once, and [Playfair::keyword] gives the matrix read row by row. An uppercase
+    /// matrix gives an uppercase cipher, and a matrix mixing cases is rejected with
+    /// [MatrixError::MixedCase].
+    pub fn decoder_from_matrix(m: Matrix) -> Result<Playfair, MatrixError> {
+        let keyword = Keyword::from_cells(std::array::from_fn(|idx| m[idx % 5][idx / 5]))?;
+        let mut pf = Playfair::from_keyword(keyword);
+
+        let upper = m[0][0].is_ascii_uppercase();
+        if let Some(pos) = (0..25)
+            .map(|idx| (idx % 5, idx / 5))
+            .find(|&(x, y)| m[x][y].is_ascii_uppercase() != upper)
+        {
+            return Err(MatrixError::MixedCase(pos));
+        }
+
+        if upper {
+            pf.set_matrix_case(MatrixCase::Upper);
+        }
+
+        Ok(pf)
+    }
+
+    /// Construct a cipher from a matrix built by [const_matrix], which was already validated so
+    /// isn't checked again outside of debug builds.
+    pub fn from_const_matrix(m: Matrix) -> Playfair {
+        let cells: [char; 25] = std::array::from_fn(|idx| m[idx % 5][idx / 5]);
+        debug_assert!(Keyword::from_cells(cells).is_ok(), "invalid matrix");
+
+        let mut pf = Playfair::from_keyword(Keyword(cells.iter().collect()));
+        pf.matrix = OnceLock::from(Arc::new(m));
+        pf
+    }
+
+    /// Construct a cipher that reads through a shared matrix, rather than owning a copy of its
+    /// own, e.g. for a server creating many instances with the same key. The matrix is validated
+    /// like [Playfair::decoder_from_matrix]. Changing the [MatrixCase] or [MatrixLayout] of an
+    /// instance gives it its own copy, leaving the shared matrix alone.
+    pub fn from_shared(matrix: Arc<Matrix>) -> Result<Playfair, MatrixError> {
+        let mut pf = Playfair::decoder_from_matrix(*matrix)?;
+        pf.matrix = OnceLock::from(matrix);
+
+        Ok(pf)
+    }
+
+    /// Get a handle to the matrix that can be shared between instances with
+    /// [Playfair::from_shared].
+    pub fn shared_matrix(&self) -> Arc<Matrix> {
+        self.matrix();
+        Arc::clone(self.matrix.get().expect("the matrix was just built"))
+    }
+
+    /// Get a fingerprint of the key, a hash of the matrix that can be stored in audit logs to
+    /// correlate which key encrypted which message without revealing the key itself. The same
+    /// matrix always gives the same fingerprint, regardless of its [MatrixCase].
+    ///
+    /// This is a 64-bit FNV-1a hash, which isn't cryptographic. Since there are comparatively
+    /// few keys, a fingerprint could be reversed by brute force.
+    pub fn fingerprint(&self) -> u64 {
+        fnv1a(&self.to_bytes().to_ascii_lowercase())
+    }
+
+    /// Encrypt a plaintext, also returning the [Playfair::fingerprint] of the key used.
+    pub fn encrypt_with_fingerprint(&self, plaintext: &str) -> (String, u64) {
+        (self.encrypt(plaintext), self.fingerprint())
+    }
+
+    /// Get the matrix that encryption/decryption is operated over, constructing it from the
+    /// keyword if this is the first time it is needed.
+    pub fn matrix(&self) -> &Matrix {
+        self.matrix.get_or_init(|| {
+            let mut matrix = self.keyword.to_matrix_with_case(self.case);
+            self.layout.arrange(&mut matrix);
+
+            Arc::new(matrix)
+        })
+    }
+
+    /// Get the matrix as nested vectors, indexed the same way as [Playfair::matrix]. This is
+    /// handy for rendering code that works with grids of any size, see also
+    /// [Playfair7::matrix_vec].
+    pub fn matrix_vec(&self) -> Vec<Vec<char>> {
+        self.matrix().iter().map(|column| column.to_vec()).collect()
+    }
+
+    /// Set the order the keyword is laid out into the matrix in, see [MatrixLayout].
+    pub fn set_matrix_layout(&mut self, layout: MatrixLayout) {
+        // Undo the current layout and apply the new one, which also works for a transformed
+        // matrix. If the matrix hasn't been built yet, it will be in the new layout when it is.
+        if let Some(matrix) = self.matrix.get_mut() {
+            // A shared matrix is copied rather than changed for every instance sharing it
+            let matrix = Arc::make_mut(matrix);
+            self.layout.arrange(matrix);
+            layout.arrange(matrix);
+        }
+
+        self.layout = layout;
+    }
+
+    /// Set the letter case the matrix is stored in, and therefore the case of the output of
+    /// encryption/decryption. Input is accepted in either case regardless.
+    pub fn set_matrix_case(&mut self, case: MatrixCase) {
+        self.case = case;
+
+        if self.canonical {
+            // The matrix will be rebuilt in the new case when next needed
+            self.matrix = OnceLock::new();
+        } else if let Some(matrix) = self.matrix.get_mut() {
+            // A transformed matrix can't be rebuilt from the keyword, so convert it in place
+            for chr in Arc::make_mut(matrix).iter_mut().flatten() {
+                *chr = match case {
+                    MatrixCase::Lower => chr.to_ascii_lowercase(),
+                    MatrixCase::Upper => chr.to_ascii_uppercase(),
+                };
+            }
+        }
+    }
+
+    /// Set the convention the rectangle rule follows, see [RectangleRule].
+    pub fn set_rectangle_rule(&mut self, rule: RectangleRule) {
+        self.rectangle_rule = rule;
+    }
+
+    /// Set what happens to digits in the plaintext, see [DigitPolicy].
+    pub fn set_digit_policy(&mut self, policy: DigitPolicy) {
+        self.digit_policy = policy;
+    }
+
+    /// Set how many cells the same row and same column rules move letters along, instead of the
+    /// usual 1. Encryption moves letters forward by the shift and decryption back by it, so any
+    /// shift from 1 to 4 is invertible, anything else is rejected with [ShiftError::OutOfRange].
+    /// Both parties need to use the same shift.
+    pub fn set_shift(&mut self, shift: usize) -> Result<(), ShiftError> {
+        if !(1..5).contains(&shift) {
+            return Err(ShiftError::OutOfRange(shift));
+        }
+
+        self.shift = shift;
+        Ok(())
+    }
+
+    /// Set how the seperator between doubled letters is chosen, see [SeparatorStrategy].
+    pub fn set_separator_strategy(&mut self, strategy: SeparatorStrategy) {
+        self.separator_strategy = strategy;
+    }
+
+    /// Set the fillers [SeparatorStrategy::Priority] chooses from, in order of preference, which
+    /// is [DEFAULT_SEPARATOR_PRIORITY] by default. Characters outside of the matrix alphabet are
+    /// rejected with [MatrixError::InvalidCharacter]. Both parties need to use the same list.
+    pub fn set_separator_priority(&mut self, priority: &[char]) -> Result<(), MatrixError> {
+        let priority = priority
+            .iter()
+            .map(|&c| match c.to_ascii_lowercase() {
+                lower if ALPHABET.contains(lower) => Ok(lower),
+                _ => Err(MatrixError::InvalidCharacter(c)),
+            })
+            .collect::<Result<_, _>>()?;
+
+        self.separator_priority = priority;
+        Ok(())
+    }
+
+    /// Set how input is grouped into bigrams, see [BigramMode]. Both parties need to use the
+    /// same mode.
+    pub fn set_bigram_mode(&mut self, mode: BigramMode) {
+        self.bigram_mode = mode;
+    }
+
+    /// Borrow this cipher as a [PlayfairView].
+    pub fn view(&self) -> PlayfairView<'_> {
+        PlayfairView(self)
+    }
+
+    /// Get the encryption and decryption of this cipher as a pair of closures, so they can be
+    /// passed to higher-order code as plain functions without carrying the struct or the
+    /// [Cipher] trait along.
+    pub fn as_codec(&self) -> (impl Fn(&str) -> String + '_, impl Fn(&str) -> String + '_) {
+        (
+            |plaintext: &str| self.encrypt(plaintext),
+            |ciphertext: &str| self.decrypt(ciphertext),
+        )
+    }
+
+    /// Encrypt a plaintext lazily, yielding the ciphertext one character at a time. Nothing is
+    /// materialized up front, so this composes with the rest of the [Iterator] adapters, e.g.
+    /// `.take()` to only encrypt the start of a long message.
+    pub fn encrypt_chars<'a>(&'a self, plaintext: &'a str) -> impl Iterator<Item = char> + 'a {
+        self.bigrams(plaintext).flat_map(move |bigram| {
+            let (a, b) = self.encrypt_bigram(bigram);
+            [a, b]
+        })
+    }
+
+    /// Encrypt a plaintext and format the ciphertext for presentation, e.g. as the traditional
+    /// uppercase groups of five letters. Formatted ciphertext can be passed straight to
+    /// [Cipher::decrypt], since decryption ignores case and anything that isn't a letter.
+    pub fn encrypt_formatted(&self, plaintext: &str, opts: FormatOptions) -> String {
+        let mut buffer = String::with_capacity(self.ciphertext_len(plaintext) * 2);
+
+        for (idx, c) in self.encrypt_chars(plaintext).enumerate() {
+            if idx > 0
+                && opts
+                    .group_size
+                    .is_some_and(|size| size > 0 && idx % size == 0)
+            {
+                buffer.push(opts.group_sep);
+            }
+
+            buffer.push(if opts.uppercase {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            });
+        }
+
+        buffer
+    }
+
+    /// Check a ciphertext received over some channel against the plaintext it should hold, e.g.
+    /// while setting up a link where both ends know a test message. The plaintext is encrypted
+    /// and compared letter by letter with the received ciphertext, ignoring case and anything
+    /// that isn't a letter, pinpointing where it was corrupted.
+    pub fn verify_transmission(
+        &self,
+        plaintext: &str,
+        received_ciphertext: &str,
+    ) -> TransmissionReport {
+        let expected = self.encrypt(plaintext);
+        let received: String = Playfair::normalize(received_ciphertext).collect();
+
+        let (a, b): (Vec<char>, Vec<char>) = (
+            expected.to_ascii_lowercase().chars().collect(),
+            received.chars().collect(),
+        );
+        let mismatches = (0..a.len().max(b.len()))
+            .filter(|&idx| a.get(idx) != b.get(idx))
+            .collect();
+
+        TransmissionReport {
+            expected,
+            received,
+            mismatches,
+        }
+    }
+
+    /// Count how often each rule applies across the bigrams of a plaintext, as
+    /// `(same column, same row, rectangle)`, showing how a message exercises the key.
+    pub fn rule_histogram(&self, plaintext: &str) -> (usize, usize, usize) {
+        self.bigrams(plaintext)
+            .fold((0, 0, 0), |(column, row, rectangle), bigram| {
+                match self.case_for(bigram) {
+                    SubstitutionCase::SameColumn => (column + 1, row, rectangle),
+                    SubstitutionCase::SameRow => (column, row + 1, rectangle),
+                    SubstitutionCase::Rectangle => (column, row, rectangle + 1),
+                }
+            })
+    }
+
+    /// Encrypt a plaintext, also gathering [EncryptStats] on how the plaintext was turned into
+    /// the ciphertext, e.g. for telemetry.
+    pub fn encrypt_stats(&self, plaintext: &str) -> EncryptStats {
+        let ciphertext = self.encrypt(plaintext);
+        let output_len = ciphertext.chars().count();
+
+        // A character is dropped if it normalizes to nothing at all
+        let dropped = plaintext
+            .char_indices()
+            .filter(|&(idx, c)| {
+                self.plaintext_chars(&plaintext[idx..idx + c.len_utf8()])
+                    .next()
+                    .is_none()
+            })
+            .count();
+
+        EncryptStats {
+            input_len: plaintext.chars().count(),
+            dropped,
+            fillers_inserted: output_len - self.plaintext_chars(plaintext).count(),
+            output_len,
+            ciphertext,
+        }
+    }
+
+    /// Encrypt a plaintext into the one authoritative form of its ciphertext: lowercase and
+    /// ungrouped, regardless of the [MatrixCase]. The same key and plaintext always give the same
+    /// canonical ciphertext, so it can be compared reliably, see [Playfair::canonicalize] to
+    /// bring formatted ciphertext into the same form.
+    pub fn canonical_ciphertext(&self, plaintext: &str) -> String {
+        self.encrypt_chars(plaintext)
+            .map(|c| c.to_ascii_lowercase())
+            .collect()
+    }
+
+    /// Bring a ciphertext in any format, e.g. from [Playfair::encrypt_formatted], into the
+    /// canonical form of [Playfair::canonical_ciphertext] by lowercasing it and removing
+    /// anything that isn't a letter.
+    pub fn canonicalize(ciphertext: &str) -> String {
+        Playfair::normalize(ciphertext).collect()
+    }
+
+    /// Encrypt a plaintext without any data dependent branching, producing the same output as
+    /// [Cipher::encrypt]. Every character is looked up by scanning the whole matrix, and all three
+    /// rules are computed for each bigram with the applicable one selected by index arithmetic, so
+    /// the time taken only depends on the length of the input.
+    ///
+    /// Note that this only reduces timing variation, it does not make the Playfair cipher secure.
+    /// It is trivially broken by frequency analysis and should not be used to protect anything
+    /// of value.
+    pub fn encrypt_stable(&self, plaintext: &str) -> String {
+        let matrix = self.matrix();
+        let mut buffer = String::with_capacity(self.ciphertext_len(plaintext));
+
+        for (a, b) in self.bigrams(plaintext) {
+            let a_pos = self.get_position_stable(a);
+            let b_pos = self.get_position_stable(b);
+
+            // Compute the result of every rule
+            let shift = self.shift;
+            let candidates = [
+                (
+                    (a_pos.0, (a_pos.1 + shift) % 5),
+                    (b_pos.0, (b_pos.1 + shift) % 5),
+                ),
+                (
+                    ((a_pos.0 + shift) % 5, a_pos.1),
+                    ((b_pos.0 + shift) % 5, b_pos.1),
+                ),
+                self.rectangle_positions(a_pos, b_pos),
+            ];
+
+            // Then select which one applies, 0 for the same column, 1 for the same row, and 2
+            // for the rectangle.
+            let same_column = (a_pos.0 == b_pos.0) as usize;
+            let same_row = (a_pos.1 == b_pos.1) as usize & (1 - same_column);
+            let rectangle = 1 - same_column - same_row;
+            let (a_out, b_out) = candidates[same_row + 2 * rectangle];
+
+            buffer.push(matrix[a_out.0][a_out.1]);
+            buffer.push(matrix[b_out.0][b_out.1]);
+        }
+
+        buffer
+    }
+
+    /// Encrypt a plaintext, describing every step taken along the way rather than only producing
+    /// the ciphertext. Each [BigramStep] holds the plaintext pair, where it sits in the matrix,
+    /// which rule applied, and the resulting ciphertext pair, which is enough to drive a
+    /// step-by-step visualization of the cipher.
+    pub fn encrypt_annotated(&self, plaintext: &str) -> Vec<BigramStep> {
+        self.bigrams(plaintext)
+            .map(|bigram| self.trace_bigram(bigram))
+            .collect()
+    }
+
+    /// Produce a step-by-step worked example of encrypting a plaintext as a Markdown table, with
+    /// a row for each bigram giving its letters, their positions in the matrix as `(x, y)`, the
+    /// rule that applied, and the resulting ciphertext pair. This is handy for documentation and
+    /// teaching handouts.
+    pub fn worked_example(&self, plaintext: &str) -> String {
+        let mut buffer = String::from(
+            "| Bigram | Positions | Rule | Output |\n| ------ | --------- | ---- | ------ |\n",
+        );
+
+        for step in self.encrypt_annotated(plaintext) {
+            let ((ax, ay), (bx, by)) = step.input_positions;
+            let rule = match step.case {
+                SubstitutionCase::SameColumn => "same column",
+                SubstitutionCase::SameRow => "same row",
+                SubstitutionCase::Rectangle => "rectangle",
+            };
+
+            buffer.push_str(&format!(
+                "| {}{} | ({ax}, {ay}) ({bx}, {by}) | {rule} | {}{} |\n",
+                step.input.0, step.input.1, step.output.0, step.output.1
+            ));
+        }
+
+        buffer
+    }
+
+    /// Encrypt a plaintext into a debugging format, where each ciphertext pair is followed by a
+    /// symbol for the rule that produced it and pairs are separated by spaces: `|` for the same
+    /// column, `-` for the same row, and `#` for the rectangle (rather than a letter, so it can't
+    /// be mistaken for ciphertext). The wiki example starts "bm# od| zb# ...".
+    pub fn encrypt_debug(&self, plaintext: &str) -> String {
+        self.encrypt_annotated(plaintext)
+            .iter()
+            .map(|step| {
+                let symbol = match step.case {
+                    SubstitutionCase::SameColumn => '|',
+                    SubstitutionCase::SameRow => '-',
+                    SubstitutionCase::Rectangle => '#',
+                };
+
+                format!("{}{}{symbol}", step.output.0, step.output.1)
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Encrypt a list of bigrams exactly as given, for callers doing their own segmentation of
+    /// the plaintext. No seperators or padding are inserted, so a doubled letter shares both a
+    /// row and a column with itself, and is encrypted with the same column rule. Letters are
+    /// looked up case-insensitively with 'j' taken as 'i', and any other character that isn't in
+    /// the matrix fails with [MatrixError::InvalidCharacter].
+    pub fn encrypt_bigrams_explicit(&self, bigrams: &[Bigram]) -> Result<String, MatrixError> {
+        let mut buffer = String::with_capacity(bigrams.len() * 2);
+
+        for &(a, b) in bigrams {
+            for c in [a, b] {
+                let lower = c.to_ascii_lowercase();
+                if lower != 'j' && !ALPHABET.contains(lower) {
+                    return Err(MatrixError::InvalidCharacter(c));
+                }
+            }
+
+            let (a, b) = self.encrypt_bigram((a, b));
+            buffer.push(a);
+            buffer.push(b);
+        }
+
+        Ok(buffer)
+    }
+
+    /// Encrypt a single bigram exactly as given, without any normalization, seperators, or
+    /// padding. Letters are looked up case-insensitively, with 'j' taken as 'i'.
+    pub fn map_bigram(&self, bigram: Bigram) -> Bigram {
+        self.encrypt_bigram(bigram)
+    }
+
+    /// Determine which rule of the cipher applies to a bigram, without computing the result.
+    pub fn case_for(&self, b: Bigram) -> SubstitutionCase {
+        SubstitutionCase::of(
+            self.get_position_in_matrix(&b.0),
+            self.get_position_in_matrix(&b.1),
+        )
+    }
+
+    /// Encrypt a plaintext, calling the observer with a [BigramEvent] for every bigram as it is
+    /// encrypted. This allows hooking in logging, metrics, or visualization without the crate
+    /// depending on any of them.
+    pub fn encrypt_with_observer(
+        &self,
+        plaintext: &str,
+        observer: &mut dyn FnMut(BigramEvent),
+    ) -> String {
+        let mut buffer = String::with_capacity(self.ciphertext_len(plaintext));
+
+        for bigram in self.bigrams(plaintext) {
+            let step = self.trace_bigram(bigram);
+
+            observer(BigramEvent {
+                input: step.input,
+                case: step.case,
+                output: step.output,
+            });
+
+            buffer.push(step.output.0);
+            buffer.push(step.output.1);
+        }
+
+        buffer
+    }
+
+    /// Describe how a single bigram is encrypted.
+    pub fn trace_bigram(&self, bigram: Bigram) -> BigramStep {
+        let input_positions = (
+            self.get_position_in_matrix(&bigram.0),
+            self.get_position_in_matrix(&bigram.1),
+        );
+
+        BigramStep {
+            input: bigram,
+            input_positions,
+            case: SubstitutionCase::of(input_positions.0, input_positions.1),
+            output: self.encrypt_bigram(bigram),
+            output_positions: self.encrypt_positions(bigram),
+        }
+    }
+
+    /// Encrypt with this cipher, then encrypt the result again with `second`. The first pass
+    /// never outputs a bigram of doubled letters (unless using [BigramMode::NoSeparator]) and is
+    /// always of even length, so the second pass lines up with the first one's bigrams and adds
+    /// no seperators or padding. Use [Playfair::decrypt_double] to reverse it.
+    ///
+    /// The security benefit is limited: both passes work on the same bigrams, so the composition
+    /// is still a single substitution of bigrams, open to the same frequency analysis as one
+    /// Playfair key. It only removes the structural regularities of the Playfair rules.
+    pub fn encrypt_double(&self, plaintext: &str, second: &Playfair) -> String {
+        second.encrypt(&self.encrypt(plaintext))
+    }
+
+    /// Reverse [Playfair::encrypt_double], decrypting with `second` first and then with this
+    /// cipher.
+    pub fn decrypt_double(&self, ciphertext: &str, second: &Playfair) -> String {
+        self.decrypt(&second.decrypt(ciphertext))
+    }
+
+    /// Encrypt two messages into a single ciphertext, alternating between a bigram of `msg_a`
+    /// and a bigram of `msg_b`. Each message is split into bigrams independently under this
+    /// cipher's options, so both can be recovered with [Playfair::decrypt_interleaved].
+    ///
+    /// The two messages should produce the same number of bigrams. If they don't, the shorter
+    /// one is extended with bigrams of [DEFAULT_PAD] followed by the cipher's padding for it to
+    /// match, which will show up at the end of its decryption.
+    pub fn encrypt_interleaved(&self, msg_a: &str, msg_b: &str) -> String {
+        let mut a = self.bigrams(msg_a);
+        let mut b = self.bigrams(msg_b);
+        let filler = (DEFAULT_PAD, self.pad(DEFAULT_PAD));
+        let mut buffer = String::new();
+
+        loop {
+            let next = [a.next(), b.next()];
+
+            // Stop once both messages have run out
+            if next.iter().all(Option::is_none) {
+                break;
+            }
+
+            for bigram in next {
+                let (c, d) = self.encrypt_bigram(bigram.unwrap_or(filler));
+
+                buffer.push(c);
+                buffer.push(d);
+            }
+        }
+
+        buffer
+    }
+
+    /// Decrypt a ciphertext produced by [Playfair::encrypt_interleaved], separating it back
+    /// into the two original messages.
+    pub fn decrypt_interleaved(&self, ciphertext: &str) -> (String, String) {
+        let mut msg_a = String::new();
+        let mut msg_b = String::new();
+
+        // The padding bigrams encrypt to a doubled letter, so the ciphertext must be split into
+        // pairs as-is rather than bigramified.
+        let letters: Vec<char> = Playfair::normalize(ciphertext).collect();
+
+        for (idx, pair) in letters.chunks(2).enumerate() {
+            let (c, d) = self.decrypt_bigram((pair[0], *pair.get(1).unwrap_or(&DEFAULT_PAD)));
+            // Even bigrams belong to the first message, odd ones to the second
+            let buffer = if idx % 2 == 0 { &mut msg_a } else { &mut msg_b };
+
+            buffer.push(c);
+            buffer.push(d);
+        }
+
+        (msg_a, msg_b)
+    }
+
+    /// Encrypt a message prefixed by an indicator group, as was done in historical traffic. The
+    /// indicator is split into bigrams (and padded) on its own, followed by the
+    /// [INDICATOR_SEPARATOR] bigram and then the message, all encrypted together.
+    ///
+    /// An indicator containing the separator as one of its bigrams can't be split back out
+    /// correctly by [Playfair::decrypt_with_indicator].
+    pub fn encrypt_with_indicator(&self, plaintext: &str, indicator: &str) -> String {
+        let (a, b) = self.encrypt_bigram(INDICATOR_SEPARATOR);
+
+        format!(
+            "{}{a}{b}{}",
+            self.encrypt(indicator),
+            self.encrypt(plaintext)
+        )
+    }
+
+    /// Decrypt a ciphertext produced by [Playfair::encrypt_with_indicator], giving the indicator
+    /// and the message, or `None` if the ciphertext holds no [INDICATOR_SEPARATOR] bigram. Like
+    /// [Cipher::decrypt], any padding and seperators are left in both parts.
+    pub fn decrypt_with_indicator(&self, ciphertext: &str) -> Option<(String, String)> {
+        let plaintext: Vec<char> = self.decrypt(ciphertext).chars().collect();
+        let separator = [INDICATOR_SEPARATOR.0, INDICATOR_SEPARATOR.1];
+
+        let idx = plaintext
+            .chunks(2)
+            .position(|pair| pair.iter().map(char::to_ascii_lowercase).eq(separator))?;
+
+        Some((
+            plaintext[..2 * idx].iter().collect(),
+            plaintext[2 * idx + 2..].iter().collect(),
+        ))
+    }
+
+    /// Encrypt only the parts of a text outside of already encrypted regions, which are wrapped in
+    /// `marker_open` and `marker_close` (e.g. `[enc]...[/enc]`). Marked regions are copied to the
+    /// output verbatim, markers included, while each stretch of text between them is encrypted on
+    /// its own. An opening marker without a matching closing marker is treated as plain text.
+    pub fn encrypt_selective(&self, text: &str, marker_open: &str, marker_close: &str) -> String {
+        // Empty markers can't delimit anything
+        if marker_open.is_empty() || marker_close.is_empty() {
+            return self.encrypt(text);
+        }
+
+        let mut buffer = String::new();
+        let mut rest = text;
+
+        while let Some(start) = rest.find(marker_open) {
+            // Find the end of the marked region, searching after the opening marker
+            let after_open = start + marker_open.len();
+            let Some(end) = rest[after_open..].find(marker_close) else {
+                break;
+            };
+            let end = after_open + end + marker_close.len();
+
+            buffer.push_str(&self.encrypt(&rest[..start]));
+            buffer.push_str(&rest[start..end]);
+            rest = &rest[end..];
+        }
+
+        buffer.push_str(&self.encrypt(rest));
+        buffer
+    }
+
+    /// Encrypt a plaintext, also reporting whether the ciphertext happens to contain any of the
+    /// words of a blocklist (compared regardless of case), e.g. so a classroom tool can pick
+    /// different wording or padding and try again rather than show something offensive.
+    pub fn encrypt_filtered(&self, plaintext: &str, blocklist: &HashSet<String>) -> (String, bool) {
+        let ciphertext = self.encrypt(plaintext);
+        let lowercase = ciphertext.to_lowercase();
+        let blocked = blocklist
+            .iter()
+            .any(|word| !word.is_empty() && lowercase.contains(&word.to_lowercase()));
+
+        (ciphertext, blocked)
+    }
+
+    /// Encrypt a plaintext so that none of the `forbidden` letters appear in the ciphertext,
+    /// e.g. to avoid a letter that is hard to transmit. This is experimental.
+    ///
+    /// Whenever a bigram would encrypt to a forbidden letter, its first letter is instead paired
+    /// with a filler letter (the cipher's seperator for it if possible, otherwise the first
+    /// suitable letter of the alphabet), and the second letter starts the next bigram. This
+    /// changes the structure of the ciphertext, the decryption holds the fillers amongst the
+    /// message, and telling them apart from the message requires knowing the same forbidden
+    /// letters. If no filler avoids the forbidden letters, the usual bigram is kept.
+    pub fn encrypt_avoiding(&self, plaintext: &str, forbidden: &[char]) -> String {
+        let is_allowed = |(c, d): Bigram| {
+            !forbidden
+                .iter()
+                .any(|f| f.eq_ignore_ascii_case(&c) || f.eq_ignore_ascii_case(&d))
+        };
+
+        let mut letters = self.plaintext_chars(plaintext).peekable();
+        let mut buffer = String::with_capacity(self.ciphertext_len(plaintext));
+
+        while let Some(a) = letters.next() {
+            // The bigram as it would usually be formed, and whether it uses up the next letter
+            let (mut bigram, mut pairs_next) = match letters.peek() {
+                Some(&b) if b != a || self.bigram_mode == BigramMode::NoSeparator => ((a, b), true),
+                Some(_) => ((a, self.separator(a)), false),
+                None => ((a, self.pad(a)), false),
+            };
+
+            if !is_allowed(self.encrypt_bigram(bigram)) {
+                let filler = std::iter::once(self.separator(a))
+                    .chain(ALPHABET.chars())
+                    .find(|&f| f != a && is_allowed(self.encrypt_bigram((a, f))));
+
+                if let Some(f) = filler {
+                    bigram = (a, f);
+                    pairs_next = false;
+                }
+            }
+
+            if pairs_next {
+                letters.next();
+            }
+
+            let (c, d) = self.encrypt_bigram(bigram);
+            buffer.push(c);
+            buffer.push(d);
+        }
+
+        buffer
+    }
+
+    /// Encrypt a mail-merge style template, leaving placeholder tokens such as `{name}` intact so
+    /// they can be filled in later, see [Playfair::encrypt_selective]. The text around each token
+    /// is encrypted on its own, so the recipient decrypts each stretch between the tokens.
+    pub fn encrypt_template(&self, template: &str, token_delims: (char, char)) -> String {
+        let (open, close) = token_delims;
+        self.encrypt_selective(template, &open.to_string(), &close.to_string())
+    }
+
+    /// Generate `(plaintext, ciphertext)` test vectors for each of the samples under this
+    /// cipher, so downstream projects can check their own implementations against this one.
+    pub fn generate_test_vectors(&self, samples: &[&str]) -> Vec<(String, String)> {
+        samples
+            .iter()
+            .map(|sample| (sample.to_string(), self.encrypt(sample)))
+            .collect()
+    }
+
+    /// Pack a ciphertext into bytes using under 5 bits per letter, for compact storage or
+    /// transmission. Since 25^3 < 2^14, each group of 3 letters (from the 25 letter matrix
+    /// alphabet) is packed into 14 bits, about 4.67 bits per letter. The ciphertext is normalized
+    /// first, dropping any letter outside of 'a' to 'z' (such as an 'ø') since it can't be packed,
+    /// and the number of letters has to be kept alongside the bytes for
+    /// [Playfair::unpack_ciphertext].
+    pub fn pack_ciphertext(ciphertext: &str) -> Vec<u8> {
+        let letters: Vec<u32> = Playfair::normalize(ciphertext)
+            .filter_map(|c| ALPHABET.find(if c == 'j' { 'i' } else { c }))
+            .map(|idx| idx as u32)
+            .collect();
+        let mut packed = Vec::with_capacity((letters.len().div_ceil(3) * 14).div_ceil(8));
+        // Bits waiting to be written out, and how many of them there are
+        let (mut bits, mut count) = (0u32, 0);
+
+        for group in letters.chunks(3) {
+            // A short final group is filled out with 'a's
+            let value = (0..3).fold(0, |acc, idx| acc * 25 + group.get(idx).unwrap_or(&0));
+
+            bits = bits << 14 | value;
+            count += 14;
+            while count >= 8 {
+                count -= 8;
+                packed.push((bits >> count) as u8);
+            }
+        }
+
+        if count > 0 {
+            packed.push((bits << (8 - count)) as u8);
+        }
+
+        packed
+    }
+
+    /// Unpack the first `len` letters of a ciphertext packed by [Playfair::pack_ciphertext], in
+    /// lowercase. Stops early if the bytes run out.
+    pub fn unpack_ciphertext(packed: &[u8], len: usize) -> String {
+        let alphabet: Vec<char> = ALPHABET.chars().collect();
+        let mut buffer = String::with_capacity(len);
+        let (mut bits, mut count) = (0u32, 0);
+
+        for &byte in packed {
+            bits = bits << 8 | byte as u32;
+            count += 8;
+
+            if count >= 14 {
+                count -= 14;
+                let value = (bits >> count) & 0x3fff;
+
+                for place in [625, 25, 1] {
+                    buffer.push(alphabet[(value / place % 25) as usize]);
+                }
+            }
+        }
+
+        buffer.chars().take(len).collect()
+    }
+
+    /// Time encrypting a sample `iterations` times, giving the total time taken. This is a simple
+    /// self-benchmark for where criterion isn't available, e.g. asserting in CI that throughput
+    /// hasn't regressed by orders of magnitude. Timings are noisy, so compare them generously.
+    pub fn throughput_sample(&self, sample: &str, iterations: usize) -> Duration {
+        let start = Instant::now();
+
+        for _ in 0..iterations {
+            // Stop the compiler from optimizing the unused encryption away
+            std::hint::black_box(self.encrypt(std::hint::black_box(sample)));
+        }
+
+        start.elapsed()
+    }
+
+    /// Hash the `(keyword, plaintext, ciphertext)` triple of encrypting a plaintext, so a test
+    /// suite can pin down the behavior of a key with a single golden number. The three strings
+    /// are hashed with 64-bit FNV-1a, each followed by a zero byte so they can't run together.
+    pub fn vector_hash(&self, plaintext: &str) -> u64 {
+        let ciphertext = self.encrypt(plaintext);
+        let mut bytes = Vec::new();
+
+        for part in [self.keyword(), plaintext, &ciphertext] {
+            bytes.extend_from_slice(part.as_bytes());
+            bytes.push(0);
+        }
+
+        fnv1a(&bytes)
+    }
+
+    /// Get the exact length of the ciphertext [Cipher::encrypt] would produce for a plaintext
+    /// under this cipher's options, without performing the encryption. This accounts for dropped
+    /// characters, seperators inserted between doubled letters, and padding to an even length,
+    /// so is useful for sizing an output buffer up front.
+    pub fn ciphertext_len(&self, plaintext: &str) -> usize {
+        self.bigrams(plaintext).count() * 2
+    }
+
+    /// Generate a plaintext of `len` letters that needs the most fillers possible when grouped
+    /// into bigrams, for stress testing the padding logic. Every letter is the same, so each one
+    /// is paired with a seperator or the pad, doubling the length of the ciphertext.
+    pub fn worst_case_input(len: usize) -> String {
+        "a".repeat(len)
+    }
+
+    /// Measure how much a plaintext is altered by a round trip through encryption and decryption,
+    /// as the edit distance between the plaintext normalized under this cipher's options (so
+    /// spelled out digits aren't counted) and the decrypted ciphertext. This is zero for messages
+    /// without a 'j', doubled letters in a bigram, or an odd length, and otherwise counts the
+    /// replaced 'j's and the inserted seperators and padding.
+    pub fn lossiness_distance(&self, plaintext: &str) -> usize {
+        let normalized: Vec<char> = self.plaintext_chars(plaintext).collect();
+        let round_trip: Vec<char> = self.decrypt(&self.encrypt(plaintext)).chars().collect();
+
+        levenshtein(&normalized, &round_trip)
+    }
+
+    /// Guess whether the plaintext behind a ciphertext had an odd length, and so was padded with
+    /// a trailing [DEFAULT_PAD], or the pad [SeparatorStrategy::Priority] chooses after a
+    /// trailing pad letter. Every ciphertext has an even length whether or not it was padded, so
+    /// this looks at whether the decryption ends in the pad letter.
+    ///
+    /// This is only a heuristic: a plaintext that really ended in the pad letter (e.g. "box")
+    /// looks padded, and a sender using a different pad isn't detected.
+    pub fn likely_has_trailing_pad(&self, ciphertext: &str) -> bool {
+        let decrypted: Vec<char> = self.decrypt(ciphertext).chars().collect();
+
+        match decrypted[..] {
+            [.., prev, c] => self.is_pad(prev, c),
+            _ => false,
+        }
+    }
+
+    /// The seperator this cipher puts after a doubled letter, see [SeparatorStrategy].
+    fn separator(&self, a: char) -> char {
+        match self.separator_strategy {
+            SeparatorStrategy::Fixed => DEFAULT_SEPARATOR,
+            SeparatorStrategy::ContextDerived => context_separator(self.matrix(), a),
+            SeparatorStrategy::Priority => priority_separator(&self.separator_priority, a),
+        }
+    }
+
+    /// The padding this cipher puts after a trailing letter, see [SeparatorStrategy::Priority].
+    fn pad(&self, last: char) -> char {
+        match self.separator_strategy {
+            SeparatorStrategy::Priority => priority_pad(&self.separator_priority, last),
+            _ => DEFAULT_PAD,
+        }
+    }
+
+    /// Whether `c` is the padding this cipher puts after a trailing `prev`.
+    fn is_pad(&self, prev: char, c: char) -> bool {
+        c.eq_ignore_ascii_case(&self.pad(prev))
+    }
+
+    /// Decrypt a ciphertext and remove the fillers likely inserted during encryption, see
+    /// [Playfair::decrypt_clean_with_pad]. This assumes the sender used the same
+    /// [SeparatorStrategy] as this cipher, recomputing context derived and priority seperators,
+    /// and the [DEFAULT_PAD] or priority pad.
+    pub fn decrypt_clean(&self, ciphertext: &str) -> String {
+        match self.separator_strategy {
+            SeparatorStrategy::Fixed => {
+                self.decrypt_clean_with_pad(ciphertext, DEFAULT_PAD, DEFAULT_SEPARATOR)
+            }
+            SeparatorStrategy::ContextDerived => self.clean(
+                ciphertext,
+                |_, c| c.eq_ignore_ascii_case(&DEFAULT_PAD),
+                |prev, c| c.eq_ignore_ascii_case(&context_separator(self.matrix(), prev)),
+            ),
+            SeparatorStrategy::Priority => self.clean(
+                ciphertext,
+                |prev, c| self.is_pad(prev, c),
+                |prev, c| {
+                    c.eq_ignore_ascii_case(&priority_separator(&self.separator_priority, prev))
+                },
+            ),
+        }
+    }
+
+    /// Decrypt a ciphertext and remove the fillers likely inserted during encryption: a `sep`
+    /// ending a bigram between two identical letters, and a `pad` at the very end. The receiver
+    /// must know which pad and seperator the sender used, decryption itself doesn't need them,
+    /// but a receiver assuming the wrong ones will leave stray fillers behind.
+    ///
+    /// This is lossy in the other direction too, a genuine `sep` between identical letters (such
+    /// as the "x" of "exe") or a genuine trailing `pad` is removed as well.
+    pub fn decrypt_clean_with_pad(&self, ciphertext: &str, pad: char, sep: char) -> String {
+        self.clean(
+            ciphertext,
+            |_, c| c.eq_ignore_ascii_case(&pad),
+            |_, c| c.eq_ignore_ascii_case(&sep),
+        )
+    }
+
+    /// Decrypt a ciphertext and remove the fillers, where `is_pad` decides whether the last letter
+    /// is padding and `is_sep` whether a letter between two identical letters is a seperator,
+    /// both given the letter before it first.
+    fn clean(
+        &self,
+        ciphertext: &str,
+        is_pad: impl Fn(char, char) -> bool,
+        is_sep: impl Fn(char, char) -> bool,
+    ) -> String {
+        let letters: Vec<char> = self.decrypt(ciphertext).chars().collect();
+
+        letters
+            .iter()
+            .enumerate()
+            .filter(|&(idx, &c)| {
+                // Fillers only ever end a bigram
+                let ends_bigram = idx % 2 == 1;
+                let separator = ends_bigram
+                    && is_sep(letters[idx - 1], c)
+                    && letters
+                        .get(idx + 1)
+                        .is_some_and(|next| next.eq_ignore_ascii_case(&letters[idx - 1]));
+                let padding =
+                    ends_bigram && idx + 1 == letters.len() && is_pad(letters[idx - 1], c);
+
+                !separator && !padding
+            })
+            .map(|(_, &c)| c)
+            .collect()
+    }
+
+    /// Estimate how faithfully a ciphertext's decryption recovers the original message, as the
+    /// fraction of decrypted letters that are certain, from 0.0 to 1.0. This is a heuristic and
+    /// only looks at the decryption, counting as uncertain:
     ///
-    /// Two things to note with this, it turns everything lowercase for easier searching and
-    /// complexity, and j's are now converted to i's.
+    /// - every 'i', which may have been a 'j' in the original message.
+    /// - every [DEFAULT_SEPARATOR] between two identical letters, which was likely inserted.
+    /// - a trailing [DEFAULT_PAD], which was likely padding.
     ///
-    /// ## TODO
-    /// According to
-    /// [this](https://users.rust-lang.org/t/fast-removing-chars-from-string/24554) post, using
-    /// `.retain()` on the initial filitering we do may be faster in release builds. Investigate
-    /// more.
-    pub fn new(initial: &str) -> Self {
-        // Create a string with the capacity of 25 since we know how big this will be. This will
-        // eliminate the need for a reallocation, if Rust defaults the capacity to less than 25.
-        let mut buffer = String::with_capacity(25);
-
-        // Ensure we only take the alphabetic parts of the input string and
-        // remove any instance of 'j'.
-        let mut parsed: String = initial
-            .to_lowercase()
+    /// An empty ciphertext is trivially recovered, giving 1.0.
+    pub fn estimate_recovery_fidelity(&self, ciphertext: &str) -> f64 {
+        let letters: Vec<char> = self
+            .decrypt(ciphertext)
             .chars()
-            .filter(|c| c.is_alphabetic() && *c != 'j')
+            .map(|c| c.to_ascii_lowercase())
             .collect();
 
-        // Append the alphabet (equating 'i' = 'j', thus omitting 'j') to the initial input, to fill in the rest of the possible letters
-        // that the initial input might not cover.
-        parsed.push_str("abcdefghiklmnopqrstuvwxyz");
+        if letters.is_empty() {
+            return 1.0;
+        }
 
-        // We only need 25 letters, so keep pushing to the buffer while we have less than 25
-        // characters.
-        while buffer.len() < 25 {
-            // Loop over each character in the input and alphabet string, checking that the
-            // character is alphabetic since we can't use numbers of symbols in our Matrix.
-            for c in parsed.chars() {
-                // Check that the character does not exist in the buffer
-                if buffer.find(c).is_none() {
-                    // If so, push to the buffer
-                    buffer.push(c);
-                }
+        let uncertain = (0..letters.len())
+            .filter(|&idx| {
+                let c = letters[idx];
+                let separated = idx > 0
+                    && idx + 1 < letters.len()
+                    && c == DEFAULT_SEPARATOR
+                    && letters[idx - 1] == letters[idx + 1];
+                let padding = idx + 1 == letters.len() && c == DEFAULT_PAD;
+
+                c == 'i' || separated || padding
+            })
+            .count();
+
+        1.0 - uncertain as f64 / letters.len() as f64
+    }
+
+    /// Check that every bigram of the matrix alphabet, all 600 pairs of distinct letters and the
+    /// 25 pairs of the same letter, decrypts back to itself after being encrypted. This is a
+    /// self-test that would catch any asymmetry between the encryption and decryption rules,
+    /// usable in tests or as a runtime assertion.
+    pub fn verify_matrix_invertible(&self) -> bool {
+        ALPHABET.chars().all(|a| {
+            ALPHABET.chars().all(|b| {
+                let (c, d) = self.decrypt_bigram(self.encrypt_bigram((a, b)));
+                c.eq_ignore_ascii_case(&a) && d.eq_ignore_ascii_case(&b)
+            })
+        })
+    }
+
+    /// Encrypt a plaintext, appending an encrypted checksum bigram to the ciphertext so that
+    /// corruption in transmission can be detected with [Playfair::decrypt_checked].
+    pub fn encrypt_checked(&self, plaintext: &str) -> String {
+        let mut buffer = self.encrypt(plaintext);
+        // The checksum covers exactly what the recipient will decrypt, including any seperators
+        // and padding.
+        let (a, b) = self.encrypt_bigram(self.checksum(self.bigrams(plaintext)));
+
+        buffer.push(a);
+        buffer.push(b);
+        buffer
+    }
+
+    /// Decrypt a ciphertext, handling the characters that aren't letters of the matrix according
+    /// to the [DecryptUnknownPolicy], e.g. to reject hand-edited ciphertext rather than silently
+    /// decrypting something else. Only [DecryptUnknownPolicy::Error] can fail.
+    pub fn decrypt_with_policy(
+        &self,
+        ciphertext: &str,
+        policy: DecryptUnknownPolicy,
+    ) -> Result<String, DecryptError> {
+        if policy == DecryptUnknownPolicy::Drop {
+            return Ok(self.decrypt(ciphertext));
+        }
+
+        let mut letters = String::with_capacity(ciphertext.len());
+        // The characters to pass through, with the number of letters before them
+        let mut unknown = Vec::new();
+
+        for c in ciphertext.chars().filter(|c| !c.is_whitespace()) {
+            // Like the other decryptions, a 'j' is read as an 'i'
+            if c.eq_ignore_ascii_case(&'j') {
+                letters.push(if c == 'J' { 'I' } else { 'i' });
+            } else if ALPHABET.contains(c.to_ascii_lowercase()) {
+                letters.push(c);
+            } else if policy == DecryptUnknownPolicy::Error {
+                return Err(DecryptError::UnknownCharacter(c));
+            } else {
+                unknown.push((letters.len(), c));
             }
         }
 
-        // Return the generated keyword
-        Self(buffer)
+        let mut unknown = unknown.into_iter().peekable();
+        let mut buffer = String::with_capacity(ciphertext.len());
+
+        for (idx, c) in self.decrypt(&letters).chars().enumerate() {
+            while let Some((_, u)) = unknown.next_if(|(at, _)| *at == idx) {
+                buffer.push(u);
+            }
+            buffer.push(c);
+        }
+        buffer.extend(unknown.map(|(_, u)| u));
+
+        Ok(buffer)
     }
 
-    /// Convert the keyword into a 5x5 [Matrix] array type in.
-    /// TODO: This can be converted to a 1-d array
-    pub fn to_matrix(&self) -> Matrix {
-        // Initialize a matrix to null-bytes to start. They will all be overwritten
-        let mut mtx: Matrix = [['\0'; 5]; 5];
+    /// Decrypt a ciphertext that may have been mangled for readability or in transmission, with
+    /// whitespace, punctuation, or line breaks anywhere, even inside a bigram. Everything but the
+    /// letters is stripped and the letters are split into pairs as-is, rather than bigramified,
+    /// since a valid ciphertext never needs seperating. If an odd number of letters is left, a
+    /// letter was lost or added and [DecryptError::OddLength] is returned rather than a
+    /// misaligned decryption.
+    pub fn decrypt_robust(&self, ciphertext: &str) -> Result<String, DecryptError> {
+        let letters: Vec<char> = Playfair::normalize(ciphertext).collect();
+
+        if letters.len() % 2 == 1 {
+            return Err(DecryptError::OddLength(letters.len()));
+        }
 
-        for (idx, chr) in self.0.char_indices() {
-            // Perform the x-value calcuation by using modular arithmetic
-            let x = idx % 5;
-            // Perform the y-value calculation by using integer division
-            let y = idx / 5;
+        Ok(letters
+            .chunks_exact(2)
+            .flat_map(|pair| {
+                let (a, b) = self.decrypt_bigram((pair[0], pair[1]));
+                [a, b]
+            })
+            .collect())
+    }
 
-            // Set the char at the given x, y value
-            mtx[x][y] = chr;
+    /// Decrypt a ciphertext produced by [Playfair::encrypt_checked], verifying and stripping the
+    /// checksum bigram at the end of it.
+    pub fn decrypt_checked(&self, ciphertext: &str) -> Result<String, DecryptError> {
+        let mut letters: Vec<char> = Playfair::normalize(ciphertext).collect();
+
+        // The last two letters are the checksum
+        let b = letters.pop().ok_or(DecryptError::MissingChecksum)?;
+        let a = letters.pop().ok_or(DecryptError::MissingChecksum)?;
+
+        if letters.len() % 2 == 1 {
+            return Err(DecryptError::OddLength(letters.len()));
         }
 
-        // Return the matrix
-        mtx
+        // Pair the letters exactly as they arrived, the checksum covers the bigrams that were
+        // encrypted, seperators and padding included
+        let bigrams: Vec<Bigram> = letters
+            .chunks_exact(2)
+            .map(|pair| self.decrypt_bigram((pair[0], pair[1])))
+            .collect();
+
+        if self.decrypt_bigram((a, b)) == self.checksum(bigrams.iter().copied()) {
+            Ok(bigrams.into_iter().flat_map(|(c, d)| [c, d]).collect())
+        } else {
+            Err(DecryptError::ChecksumMismatch)
+        }
     }
-}
 
-/// Playfair cipher structure, stores data needed during the encryption/decryption
-pub struct Playfair {
-    /// The keyword in which we generate the matrix from.
-    keyword: Keyword,
-    /// The matrix which encryption/decryption is operated over
-    matrix: Matrix,
-}
+    /// Compute a checksum bigram over a message. Each letter is mapped to its index in the
+    /// matrix, the first checksum letter is the sum of those indices and the second is a sum
+    /// weighted by position (so transposed letters are caught too), both taken mod 25 and mapped
+    /// back to the letter at that index.
+    fn checksum(&self, bigrams: impl Iterator<Item = Bigram>) -> Bigram {
+        let mut sum = 0;
+        let mut weighted = 0;
 
-impl Cipher for Playfair {
-    /// Encryption logic for a given plaintext
-    fn encrypt(&self, plaintext: &str) -> String {
-        let mut buffer = String::new();
-        let bigrams: Vec<Bigram> = Playfair::bigramify(plaintext);
-
-        // Loop over each bigram
-        for bigram in bigrams {
-            // Get the positions of the characters, needed in performing the operations on swapping
-            // or incrementing x & y values.
-            let a_pos: Position = self.get_position_in_matrix(&bigram.0);
-            let b_pos: Position = self.get_position_in_matrix(&bigram.1);
-
-            if a_pos.0 == b_pos.0 {
-                // Case 1: They are in the same column. In this case, we increment (with wrapping)
-                // their y-values by 1.
-                buffer.push(self.matrix[a_pos.0][(a_pos.1 + 1) % 5]);
-                buffer.push(self.matrix[b_pos.0][(b_pos.1 + 1) % 5]);
-            } else if a_pos.1 == b_pos.1 {
-                // Case 2: They are in the same row. In this case, we increment (with wrapping)
-                // their x-values by 1.
-                buffer.push(self.matrix[(a_pos.0 + 1) % 5][a_pos.1]);
-                buffer.push(self.matrix[(b_pos.0 + 1) % 5][b_pos.1]);
-            } else {
-                // Case 3: They are in different rows and columns, In this case, we swap the
-                // x-values of each position and keep the same y-values.
-                buffer.push(self.matrix[b_pos.0][a_pos.1]);
-                buffer.push(self.matrix[a_pos.0][b_pos.1]);
-            }
+        for (idx, c) in bigrams.flat_map(|(a, b)| [a, b]).enumerate() {
+            let (x, y) = self.get_position_in_matrix(&c);
+            let value = y * 5 + x;
+
+            sum = (sum + value) % 25;
+            weighted = (weighted + (idx + 1) * value) % 25;
         }
 
-        buffer
+        let matrix = self.matrix();
+        (matrix[sum % 5][sum / 5], matrix[weighted % 5][weighted / 5])
     }
 
-    /// Decryption logic for a given ciphertext
-    fn decrypt(&self, ciphertext: &str) -> String {
-        let mut buffer = String::new();
-        let bigrams: Vec<Bigram> = Playfair::bigramify(ciphertext);
-
-        // Loop over the bigrams
-        for bigram in bigrams {
-            // Get the positions of the characters, needed in performing the operations on swapping
-            // or decrementing x & y values.
-            let a_pos: Position = self.get_position_in_matrix(&bigram.0);
-            let b_pos: Position = self.get_position_in_matrix(&bigram.1);
-
-            if a_pos.0 == b_pos.0 {
-                // Case 1: They are in the same column. In this case, we increment (with wrapping)
-                // their y-values by 1.
-
-                // Subtract 1, producing an optional with the value from the operation. If we try
-                // to subtract 1 from 0, .checked_sub() would result in a None being returned, in
-                // which case .unwrap_or() will give us a 4, effectively giving us this 'reverse'
-                // modular arithmetic
-                let a_y = a_pos.1.checked_sub(1).unwrap_or(4);
-                let b_y = b_pos.1.checked_sub(1).unwrap_or(4);
-
-                buffer.push(self.matrix[a_pos.0][a_y]);
-                buffer.push(self.matrix[b_pos.0][b_y]);
-            } else if a_pos.1 == b_pos.1 {
-                // Case 2: They are in the same row. In this case, we increment (with wrapping)
-                // their x-values by 1.
-
-                // Subtract 1, producing an optional with the value from the operation. If we try
-                // to subtract 1 from 0, .checked_sub() would result in a None being returned, in
-                // which case .unwrap_or() will give us a 4, effectively giving us this 'reverse'
-                // modular arithmetic
-                let a_x = a_pos.0.checked_sub(1).unwrap_or(4);
-                let b_x = b_pos.0.checked_sub(1).unwrap_or(4);
-
-                buffer.push(self.matrix[a_x][a_pos.1]);
-                buffer.push(self.matrix[b_x][b_pos.1]);
+    /// Get the matrix coordinates of each enciphered bigram of a plaintext, rather than the
+    /// letters at those coordinates. This exposes the numeric substitution underlying the cipher,
+    /// and can be fed into further coordinate-based ciphers.
+    pub fn to_coordinates(&self, plaintext: &str) -> Vec<(Position, Position)> {
+        self.bigrams(plaintext)
+            .map(|bigram| self.encrypt_positions(bigram))
+            .collect()
+    }
+
+    /// Encrypt a plaintext after surrounding it with `count` random filler letters (nulls) on
+    /// each side, obscuring the length and boundaries of the message. The nulls are chosen so
+    /// they never trigger a seperator or the trailing padding to land amongst them, meaning the
+    /// recipient can remove them again with [Playfair::decrypt_strip_nulls]. The recipient
+    /// **must** know `count` to do so. With a `count` of 0 this is the same as [Cipher::encrypt].
+    #[cfg(feature = "rand")]
+    pub fn encrypt_with_nulls<R: rand::Rng + ?Sized>(
+        &self,
+        plaintext: &str,
+        count: usize,
+        rng: &mut R,
+    ) -> String {
+        let message: Vec<char> = self.plaintext_chars(plaintext).collect();
+        let mut buffer = String::with_capacity(message.len() + 2 * count);
+
+        // Prepend the nulls, making sure the last one doesn't duplicate the start of the message
+        let mut previous = None;
+        for idx in 0..count {
+            let next = if idx + 1 == count {
+                message.first().copied()
             } else {
-                // Case 3: They are in different rows and columns, In this case, we swap the
-                // x-values of each position and keep the same y-values.
-                buffer.push(self.matrix[b_pos.0][a_pos.1]);
-                buffer.push(self.matrix[a_pos.0][b_pos.1]);
+                None
+            };
+            let null = Playfair::random_null(rng, previous, next);
+
+            buffer.push(null);
+            previous = Some(null);
+        }
+
+        // Then the message itself
+        buffer.extend(message.iter());
+        previous = message.last().copied().or(previous);
+
+        // And finally append the nulls, making sure the first one doesn't duplicate the end of
+        // the message
+        for _ in 0..count {
+            let null = Playfair::random_null(rng, previous, None);
+
+            buffer.push(null);
+            previous = Some(null);
+        }
+
+        self.encrypt(&buffer)
+    }
+
+    /// Decrypt a ciphertext produced by [Playfair::encrypt_with_nulls], removing the `count`
+    /// nulls from either side of the message. With a `count` of 0 there are no nulls to tell a
+    /// trailing [DEFAULT_PAD] apart from the end of the message, so it is kept.
+    #[cfg(feature = "rand")]
+    pub fn decrypt_strip_nulls(&self, ciphertext: &str, count: usize) -> String {
+        let mut decrypted: Vec<char> = self.decrypt(ciphertext).chars().collect();
+
+        // The nulls are never the padding character, so following one it can only be the padding
+        if count > 0
+            && decrypted
+                .last()
+                .is_some_and(|c| c.eq_ignore_ascii_case(&DEFAULT_PAD))
+        {
+            decrypted.pop();
+        }
+
+        // If there aren't enough characters, there can't be a message
+        if decrypted.len() < 2 * count {
+            return String::new();
+        }
+
+        decrypted[count..decrypted.len() - count].iter().collect()
+    }
+
+    /// Pick a random null letter that doesn't equal either of its neighbours. Neither the
+    /// [DEFAULT_PAD] nor 'j' are ever picked, since they would be confused with padding or turned
+    /// into an 'i'.
+    #[cfg(feature = "rand")]
+    fn random_null<R: rand::Rng + ?Sized>(
+        rng: &mut R,
+        previous: Option<char>,
+        next: Option<char>,
+    ) -> char {
+        /// The letters a null can be chosen from.
+        const NULLS: &[u8] = b"abcdefghiklmnopqrstuvwxyz";
+
+        loop {
+            let null = NULLS[rng.gen_range(0..NULLS.len())] as char;
+
+            if null != DEFAULT_PAD && Some(null) != previous && Some(null) != next {
+                return null;
             }
         }
+    }
 
-        buffer
+    /// Encrypt a single bigram using the three rules of the Playfair cipher.
+    fn encrypt_bigram(&self, bigram: Bigram) -> Bigram {
+        let (a_pos, b_pos) = self.encrypt_positions(bigram);
+        let matrix = self.matrix();
+
+        (matrix[a_pos.0][a_pos.1], matrix[b_pos.0][b_pos.1])
     }
-}
 
-impl Playfair {
-    /// Generates a new Playfair cipher structure with the keyword and appropriate alphabet padding to
-    /// ensure it can fit into the matrix.
-    pub fn new(kw: &str) -> Self {
-        // Generate the keyword from the given input
-        let keyword = Keyword::new(kw);
-        // Construct a matrix from the keyword.
-        let matrix = keyword.to_matrix();
+    /// Decrypt a single bigram, undoing [Playfair::encrypt_bigram].
+    fn decrypt_bigram(&self, bigram: Bigram) -> Bigram {
+        let (a_pos, b_pos) = self.decrypt_positions(bigram);
+        let matrix = self.matrix();
 
-        // Return the playfair cipher
-        Self { keyword, matrix }
+        (matrix[a_pos.0][a_pos.1], matrix[b_pos.0][b_pos.1])
     }
 
-    /// Bigramify takes in a string input, converts it to an even length, and splits the input into
-    /// groups of 2-tuples of characters. This is then used in the encryption/decryption
-    /// algorithms.
-    fn bigramify(input: &str) -> Vec<Bigram> {
-        let mut buffer: Vec<Bigram> = vec![];
-        // Ensure the input is only alphabetic
-        let mut input: String = input
-            .to_lowercase()
-            .chars()
-            .filter(|c| c.is_alphabetic())
-            .collect();
+    /// Compute the positions in the matrix that a bigram encrypts to.
+    fn encrypt_positions(&self, bigram: Bigram) -> (Position, Position) {
+        // Get the positions of the characters, needed in performing the operations on swapping
+        // or incrementing x & y values.
+        let a_pos: Position = self.get_position_in_matrix(&bigram.0);
+        let b_pos: Position = self.get_position_in_matrix(&bigram.1);
+
+        match SubstitutionCase::of(a_pos, b_pos) {
+            // Case 1: They are in the same column. In this case, we increment (with wrapping)
+            // their y-values by the shift, normally 1.
+            SubstitutionCase::SameColumn => (
+                (a_pos.0, (a_pos.1 + self.shift) % 5),
+                (b_pos.0, (b_pos.1 + self.shift) % 5),
+            ),
+            // Case 2: They are in the same row. In this case, we increment (with wrapping)
+            // their x-values by the shift, normally 1.
+            SubstitutionCase::SameRow => (
+                ((a_pos.0 + self.shift) % 5, a_pos.1),
+                ((b_pos.0 + self.shift) % 5, b_pos.1),
+            ),
+            // Case 3: They are in different rows and columns, In this case, they are replaced by
+            // the letters on the opposite corners of their rectangle.
+            SubstitutionCase::Rectangle => self.rectangle_positions(a_pos, b_pos),
+        }
+    }
 
-        // Loop over the characters of the input 2 at a time, checking that there is a next one.
-        // If there are duplicates insert an 'x' to seperate the duplicates.
-        for idx in (0..input.len()).step_by(2) {
-            let a = input.chars().nth(idx).unwrap();
+    /// Compute the positions in the matrix that a bigram decrypts to.
+    fn decrypt_positions(&self, bigram: Bigram) -> (Position, Position) {
+        // Get the positions of the characters, needed in performing the operations on swapping
+        // or decrementing x & y values.
+        let a_pos: Position = self.get_position_in_matrix(&bigram.0);
+        let b_pos: Position = self.get_position_in_matrix(&bigram.1);
+
+        match SubstitutionCase::of(a_pos, b_pos) {
+            // Case 1: They are in the same column. In this case, we decrement (with wrapping)
+            // their y-values by the shift.
+            SubstitutionCase::SameColumn => {
+                // Adding 5 - shift is the same as subtracting the shift modulo 5, without the
+                // subtraction underflowing when the position is less than the shift.
+                let a_y = (a_pos.1 + 5 - self.shift) % 5;
+                let b_y = (b_pos.1 + 5 - self.shift) % 5;
+
+                ((a_pos.0, a_y), (b_pos.0, b_y))
+            }
+            // Case 2: They are in the same row. In this case, we decrement (with wrapping)
+            // their x-values by the shift.
+            SubstitutionCase::SameRow => {
+                let a_x = (a_pos.0 + 5 - self.shift) % 5;
+                let b_x = (b_pos.0 + 5 - self.shift) % 5;
 
-            if let Some(b) = input.chars().nth(idx + 1) {
-                if a == b {
-                    input.insert(idx + 1, 'x');
-                }
+                ((a_x, a_pos.1), (b_x, b_pos.1))
             }
+            // Case 3: They are in different rows and columns, In this case, they are replaced by
+            // the letters on the opposite corners of their rectangle.
+            SubstitutionCase::Rectangle => self.rectangle_positions(a_pos, b_pos),
         }
+    }
 
-        // If we are still at an odd length, append a 0 at the end of the input.
-        if input.len() % 2 != 0 {
-            input.push('x');
+    /// Compute the positions the rectangle rule maps two letters to, following the configured
+    /// [RectangleRule]. Both conventions are their own inverse, so this is used when decrypting
+    /// too.
+    fn rectangle_positions(&self, a_pos: Position, b_pos: Position) -> (Position, Position) {
+        match self.rectangle_rule {
+            // Swap the x-values of each position and keep the same y-values.
+            RectangleRule::SwapColumns => ((b_pos.0, a_pos.1), (a_pos.0, b_pos.1)),
+            // Swap the y-values of each position and keep the same x-values.
+            RectangleRule::SwapRows => ((a_pos.0, b_pos.1), (b_pos.0, a_pos.1)),
         }
+    }
+
+    /// Check whether a text is already in the form the cipher works with: only the lowercase
+    /// letters 'a' to 'z', without any 'j'. Pipelines that normalize their input up front can use
+    /// this to skip doing so again.
+    pub fn is_normalized(text: &str) -> bool {
+        text.chars().all(|c| c.is_ascii_lowercase() && c != 'j')
+    }
+
+    /// List what the normalization does to each character it is likely to see: the ASCII
+    /// letters, digits, whitespace and punctuation. Letters are lowercased, with 'j' becoming 'i'
+    /// as the matrix has no separate cell for it, and everything else is dropped (given as
+    /// `None`) under the default [DigitPolicy].
+    pub fn normalization_table() -> Vec<(char, Option<char>)> {
+        ('a'..='z')
+            .chain('A'..='Z')
+            .chain('0'..='9')
+            .chain(" \t\n.,;:!?'\"-()".chars())
+            .map(|c| {
+                let normalized = Playfair::normalize(c.encode_utf8(&mut [0; 4]))
+                    .next()
+                    .map(|n| if n == 'j' { 'i' } else { n });
+
+                (c, normalized)
+            })
+            .collect()
+    }
+
+    /// Normalize an input for the cipher, lowercasing it and only keeping the alphabetic
+    /// characters.
+    fn normalize(input: &str) -> impl Iterator<Item = char> + '_ {
+        Playfair::normalize_with(input, DigitPolicy::Drop)
+    }
+
+    /// Normalize an input like [Playfair::normalize], handling digits according to the given
+    /// [DigitPolicy].
+    fn normalize_with(input: &str, digits: DigitPolicy) -> impl Iterator<Item = char> + '_ {
+        Playfair::decompose(input)
+            .flat_map(move |c| {
+                let name = match digits {
+                    DigitPolicy::Drop => None,
+                    DigitPolicy::SpellOut => c.to_digit(10).map(|d| DIGIT_NAMES[d as usize]),
+                };
+
+                // Either the name of the digit, or the character itself
+                name.unwrap_or_default()
+                    .chars()
+                    .chain(name.is_none().then_some(c))
+            })
+            .flat_map(char::to_lowercase)
+            .filter(|c| c.is_alphabetic())
+    }
 
-        // Again loop over the pairs, this time we are guarenteed that it is an even length so we
-        // don't need the `if let Some(_)` check.
-        for idx in (0..input.len()).step_by(2) {
-            let a = input.chars().nth(idx).unwrap();
-            let b = input.chars().nth(idx + 1).unwrap();
+    /// Decompose an input into base letters where possible, so that "café" is treated as "cafe".
+    /// The input is NFKD normalized and stripped of combining marks, after which any letter
+    /// without a reasonable Latin mapping is dropped.
+    #[cfg(feature = "unicode")]
+    fn decompose(input: &str) -> impl Iterator<Item = char> + '_ {
+        use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+        input
+            .nfkd()
+            .filter(|c| !is_combining_mark(*c) && (c.is_ascii() || !c.is_alphabetic()))
+    }
+
+    /// Without the `unicode` feature, the input is taken as-is.
+    #[cfg(not(feature = "unicode"))]
+    fn decompose(input: &str) -> impl Iterator<Item = char> + '_ {
+        input.chars()
+    }
 
-            buffer.push((a, b));
+    /// Bigramify takes in a string input and lazily splits it into groups of 2-tuples of
+    /// characters, seperating doubled letters and padding to an even length. This is then used in
+    /// the encryption/decryption algorithms.
+    fn bigramify(input: &str) -> Bigrams<impl Iterator<Item = char> + '_> {
+        Bigrams {
+            chars: Playfair::normalize(input).peekable(),
+            mode: BigramMode::Standard,
+            context: None,
+            priority: None,
         }
+    }
 
-        // Return the buffer
-        buffer
+    /// Normalize a plaintext with this cipher's fold (see [Playfair::with_fold]), or otherwise
+    /// like [Playfair::normalize] with its [DigitPolicy].
+    fn plaintext_chars<'a>(&self, input: &'a str) -> impl Iterator<Item = char> + 'a {
+        // Exactly one of these is present, chaining them avoids boxing the iterator
+        let folded = self.fold.map(|fold| input.chars().filter_map(fold));
+        let normalized = self
+            .fold
+            .is_none()
+            .then(|| Playfair::normalize_with(input, self.digit_policy));
+
+        folded
+            .into_iter()
+            .flatten()
+            .chain(normalized.into_iter().flatten())
+    }
+
+    /// Split a plaintext into bigrams like [Playfair::bigramify], normalizing it with
+    /// [Playfair::plaintext_chars] and grouping doubled letters according to this cipher's
+    /// [BigramMode].
+    fn bigrams<'a>(&self, input: &'a str) -> Bigrams<impl Iterator<Item = char> + 'a> {
+        Bigrams {
+            chars: self.plaintext_chars(input).peekable(),
+            mode: self.bigram_mode,
+            context: (self.separator_strategy == SeparatorStrategy::ContextDerived)
+                .then(|| *self.matrix()),
+            priority: (self.separator_strategy == SeparatorStrategy::Priority)
+                .then(|| self.separator_priority.clone()),
+        }
     }
 
     /// Get the position of a given character withing the matrix. Returns a [Position] type, which is an
     /// (x, y) pair of where the character is in the function. Since i = j in this implementation,
-    /// whenever the letter 'j' is searched for, just search for 'i' instead.
+    /// whenever the letter 'j' is searched for, just search for 'i' instead. The search ignores
+    /// case, so it works regardless of the [MatrixCase].
     fn get_position_in_matrix(&self, to_search: &char) -> Position {
         // Loop over each column and item.
-        for (idx, column) in self.matrix.iter().enumerate() {
+        for (idx, column) in self.matrix().iter().enumerate() {
             // Check if what we are searching for is in the Matrix. This seems to be marginally
             // faster than just another for loop and comparison.
-            if let Some(jdx) = column.iter().position(|&chr| chr == *to_search) {
+            if let Some(jdx) = column
+                .iter()
+                .position(|chr| chr.eq_ignore_ascii_case(to_search))
+            {
                 // Return the position we found.
                 return (idx, jdx);
             }
@@ -265,24 +2427,106 @@ impl Playfair {
         self.get_position_in_matrix(&'i')
     }
 
+    /// Get the letters to the right of and below a letter in the matrix (wrapping around), which
+    /// are what it encrypts to under the same row and same column rules. With a shift other
+    /// than 1 (see [Playfair::set_shift]) these are that many cells away instead. Like the rest
+    /// of encryption, 'j' is taken as 'i'.
+    pub fn neighbors(&self, c: char) -> Neighbors {
+        let (x, y) = self.get_position_in_matrix(&c);
+        let matrix = self.matrix();
+
+        Neighbors {
+            right: matrix[(x + self.shift) % 5][y],
+            below: matrix[x][(y + self.shift) % 5],
+        }
+    }
+
+    /// Get the positions of every cell in the matrix a character maps to. This is normally a
+    /// single position, but since 'i' and 'j' share a cell, searching for 'j' gives the position
+    /// of 'i'. Characters that don't map to any cell, such as digits, give no positions at all.
+    pub fn all_positions(&self, c: char) -> Vec<Position> {
+        let positions: Vec<Position> = self
+            .matrix()
+            .iter()
+            .enumerate()
+            .flat_map(|(x, column)| {
+                column
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, chr)| chr.eq_ignore_ascii_case(&c))
+                    .map(move |(y, _)| (x, y))
+            })
+            .collect();
+
+        // 'j' is merged into the cell for 'i'
+        if positions.is_empty() && c.eq_ignore_ascii_case(&'j') {
+            return self.all_positions('i');
+        }
+
+        positions
+    }
+
+    /// Get the position of a given character in the matrix like
+    /// [Playfair::get_position_in_matrix], but always scanning every cell rather than returning
+    /// early, so the time taken doesn't depend on where the character is.
+    fn get_position_stable(&self, to_search: char) -> Position {
+        let mut found = 0;
+        let mut position = 0;
+        let mut fallback = 0;
+
+        for (idx, chr) in self.matrix().iter().flatten().enumerate() {
+            let hit = chr.eq_ignore_ascii_case(&to_search) as usize;
+            let i_hit = chr.eq_ignore_ascii_case(&'i') as usize;
+
+            found |= hit;
+            position += idx * hit;
+            fallback += idx * i_hit;
+        }
+
+        // Fall back to the position of 'i' when nothing was found, as i = j
+        let idx = position * found + fallback * (1 - found);
+
+        // The matrix is stored column by column
+        (idx / 5, idx % 5)
+    }
+
     /// Get a copy of the keyword of the Playfair structure
     pub fn keyword(&self) -> &str {
         self.keyword.0.as_str()
     }
 
+    /// Update the current keyword like [Playfair::update_keyword], but validate the new keyword
+    /// first with [Keyword::try_new]. On success the previous keyword is returned, and on error
+    /// the cipher is left exactly as it was, rather than silently switching to a weak matrix
+    /// mid-session.
+    pub fn try_update_keyword(&mut self, kw: &str) -> Result<Keyword, KeywordError> {
+        let keyword = Keyword::try_new(kw)?;
+        let previous = std::mem::replace(&mut self.keyword, keyword);
+
+        // Discard the current matrix, it will be rebuilt from the new keyword when next needed
+        self.matrix = OnceLock::new();
+        self.canonical = true;
+
+        Ok(previous)
+    }
+
     /// Allow updating the current keyword of the Playfair object. This may be useful if you are
     /// encrypting and decrypting amonst multiple parties at once, and have numerous different
     /// keywords / matricies to operate over.
     pub fn update_keyword(&mut self, kw: &str) {
-        // Generate the new keyword from the input
-        let kw = Keyword::new(kw);
-        // Generate a new matrix from the keyword
-        let mx = kw.to_matrix();
+        let keyword = Keyword::new(kw);
+
+        // Updating to the same keyword would only rebuild the same matrix, unless it has been
+        // transformed and needs resetting
+        if keyword == self.keyword && self.canonical {
+            return;
+        }
 
         // Update the current keyword
-        self.keyword = kw;
-        // Update the current matrix to the new matrix
-        self.matrix = mx;
+        self.keyword = keyword;
+        // Discard the current matrix, it will be rebuilt from the new keyword when next needed
+        self.matrix = OnceLock::new();
+        self.canonical = true;
     }
 }
 
@@ -326,6 +2570,17 @@ mod tests {
         assert_eq!(kw.0, "playfirexmbcdghknoqstuvwz");
     }
 
+    #[test]
+    fn test_keyword_from_pin() {
+        // 1 moves to 'b', then 2 more to 'e', 3 more to 'i', 4 more to 'o' (there is no 'j')
+        assert_eq!(Keyword::from_pin("1234"), Keyword::new("beio"));
+        assert_eq!(Keyword::from_pin("12-34"), Keyword::from_pin("1234"));
+
+        assert_ne!(Keyword::from_pin("1234"), Keyword::from_pin("4321"));
+        assert_ne!(Keyword::from_pin("1234"), Keyword::from_pin("1235"));
+        assert_eq!(Keyword::from_pin(""), Keyword::new(""));
+    }
+
     #[test]
     fn test_keyword_one_letter_input() {
         let initial = "iiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiiii";
@@ -335,6 +2590,58 @@ mod tests {
         assert_eq!(kw.0, "iabcdefghklmnopqrstuvwxyz");
     }
 
+    #[test]
+    fn test_keyword_mostly_ordered() {
+        let kw = Keyword::new("ab");
+
+        assert_eq!(kw.longest_ordered_run(), 25);
+        assert!(kw.is_mostly_ordered());
+
+        let kw = Keyword::new("qwertyuiopasdfghklzxcvbnm");
+
+        assert!(!kw.is_mostly_ordered());
+
+        // The wiki example's longest run is "stuvw"
+        let kw = Keyword::new("playfair example");
+
+        assert_eq!(kw.longest_ordered_run(), 5);
+        assert!(!kw.is_mostly_ordered());
+    }
+
+    #[test]
+    fn test_keyword_suggest_min_length() {
+        assert_eq!(Keyword::suggest_min_length(25), 0);
+        assert_eq!(Keyword::suggest_min_length(0), 25);
+
+        // A stricter target always needs at least as long a keyword
+        for target in 1..25 {
+            assert!(Keyword::suggest_min_length(target) >= Keyword::suggest_min_length(target + 1));
+        }
+        assert!(
+            Keyword::suggest_min_length(3)
+                > Keyword::suggest_min_length(Keyword::ORDERED_RUN_THRESHOLD)
+        );
+    }
+
+    #[test]
+    fn test_keyword_minimal_for() {
+        let matrix = Keyword::new("playfair example").to_matrix();
+        let minimal = Keyword::minimal_for(&matrix).unwrap();
+
+        assert_eq!(minimal, "playfirexm");
+        assert_eq!(Keyword::new(&minimal).to_matrix(), matrix);
+
+        // The plain alphabet needs no keyword at all
+        let matrix = Keyword::new("").to_matrix();
+
+        assert_eq!(Keyword::minimal_for(&matrix), Some(String::new()));
+
+        // An invalid matrix has no keyword
+        let matrix = [['a'; 5]; 5];
+
+        assert_eq!(Keyword::minimal_for(&matrix), None);
+    }
+
     #[test]
     fn test_getting_keyword_pf_struct() {
         let initial = "playfair example";
@@ -346,7 +2653,7 @@ mod tests {
     #[test]
     fn test_bigraming_even_length() {
         let initial = "abcd";
-        let big = Playfair::bigramify(initial);
+        let big: Vec<Bigram> = Playfair::bigramify(initial).collect();
 
         assert_eq!(big, vec![('a', 'b'), ('c', 'd')]);
     }
@@ -354,15 +2661,43 @@ mod tests {
     #[test]
     fn test_bigraming_odd_length() {
         let initial = "abc";
-        let big = Playfair::bigramify(initial);
+        let big: Vec<Bigram> = Playfair::bigramify(initial).collect();
 
         assert_eq!(big, vec![('a', 'b'), ('c', 'x')]);
     }
 
+    #[test]
+    fn test_bigraming_uses_default_characters() {
+        let big: Vec<Bigram> = Playfair::bigramify("eel").collect();
+
+        assert_eq!(big, vec![('e', DEFAULT_SEPARATOR), ('e', 'l')]);
+
+        let big: Vec<Bigram> = Playfair::bigramify("e").collect();
+
+        assert_eq!(big, vec![('e', DEFAULT_PAD)]);
+    }
+
+    #[test]
+    fn test_bigramify_no_separator() {
+        let mut pf = Playfair::new("playfair example");
+        pf.set_bigram_mode(BigramMode::NoSeparator);
+
+        let big: Vec<Bigram> = pf.bigrams("hello").collect();
+        assert_eq!(big, vec![('h', 'e'), ('l', 'l'), ('o', DEFAULT_PAD)]);
+    }
+
+    #[test]
+    fn test_bigraming_cascading_doubles() {
+        let initial = "aaaa";
+        let big: Vec<Bigram> = Playfair::bigramify(initial).collect();
+
+        assert_eq!(big, vec![('a', 'x'), ('a', 'x'), ('a', 'x'), ('a', 'x')]);
+    }
+
     #[test]
     fn test_bigramming_wiki() {
         let initial = "hide the gold in the tree stump";
-        let big = Playfair::bigramify(initial);
+        let big: Vec<Bigram> = Playfair::bigramify(initial).collect();
 
         assert_eq!(
             big,
@@ -426,6 +2761,94 @@ mod tests {
         assert_eq!(pos_1, pos_2);
     }
 
+    #[test]
+    fn test_lazy_matrix_matches_eager() {
+        let initial = "playfair example";
+        let pf = Playfair::new(initial);
+
+        // Nothing is built until it's asked for
+        assert!(pf.matrix.get().is_none());
+
+        let enc = pf.encrypt("hide the gold in the tree stump");
+        assert_eq!(
+            pf.matrix.get().map(|m| **m),
+            Some(Keyword::new(initial).to_matrix())
+        );
+        assert_eq!(enc, "bmodzbxdnabekudmuixmmouvif");
+    }
+
+    #[test]
+    fn test_keyword_fill_order() {
+        let kw = Keyword::with_fill_order("play", FillOrder::Reverse);
+
+        assert_eq!(kw.0, "playzxwvutsrqonmkihgfedcb");
+        assert_eq!(
+            Keyword::with_fill_order("play", FillOrder::Forward),
+            Keyword::new("play")
+        );
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+
+        assert_eq!(levenshtein(&chars("kitten"), &chars("sitting")), 3);
+        assert_eq!(levenshtein(&chars(""), &chars("abc")), 3);
+        assert_eq!(levenshtein(&chars("same"), &chars("same")), 0);
+    }
+
+    #[test]
+    fn test_keyword_from_words() {
+        let (kw, redundant) = Keyword::from_words(&["secret", "aaa", "agent", "sea"]).unwrap();
+        assert_eq!(kw, Keyword::new("secretaaaagentsea"));
+        assert_eq!(redundant, vec![3]);
+
+        let (_, redundant) = Keyword::from_words(&["play", "aaa", "jay"]).unwrap();
+        assert_eq!(redundant, vec![1]);
+
+        let (_, redundant) = Keyword::from_words(&["quick", "jack"]).unwrap();
+        assert_eq!(redundant, vec![]);
+
+        assert_eq!(
+            Keyword::from_words(&["secret", "123"]),
+            Err(KeywordError::NoLetters)
+        );
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_keyword_new_seeded() {
+        let kw = Keyword::new_seeded("play", 42);
+
+        assert_eq!(kw, Keyword::new_seeded("play", 42));
+        assert_ne!(kw, Keyword::new_seeded("play", 43));
+        assert_ne!(kw, Keyword::new("play"));
+
+        // The keyword letters stay first, and the matrix still holds every letter once
+        assert!(kw.0.starts_with("play"));
+        let mut letters: Vec<char> = kw.0.chars().collect();
+        letters.sort_unstable();
+        assert_eq!(letters.into_iter().collect::<String>(), ALPHABET);
+        assert!(!kw.is_mostly_ordered());
+    }
+
+    #[test]
+    fn test_keyword_try_new() {
+        assert_eq!(Keyword::try_new(""), Err(KeywordError::NoLetters));
+        assert_eq!(Keyword::try_new("123 !"), Err(KeywordError::NoLetters));
+        assert_eq!(
+            Keyword::try_new("playfair example"),
+            Ok(Keyword::new("playfair example"))
+        );
+
+        // Without decomposition, letters outside 'a' to 'z' are kept and would break the matrix
+        #[cfg(not(feature = "unicode"))]
+        assert_eq!(
+            Keyword::try_new("playλ"),
+            Err(KeywordError::InvalidCharacter('λ'))
+        );
+    }
+
     #[test]
     fn test_updating_keyword() {
         let initial = "init";
@@ -438,4 +2861,29 @@ mod tests {
 
         assert_eq!(pf.keyword(), "playfirexmbcdghknoqstuvwz");
     }
+
+    #[test]
+    fn test_is_consistent_detects_desync() {
+        let mut pf = Playfair::new("playfair example");
+        pf.matrix();
+        assert!(pf.is_consistent());
+
+        // Only updating the keyword leaves the old matrix behind
+        pf.keyword = Keyword::new("monarchy");
+        assert!(!pf.is_consistent());
+    }
+
+    #[test]
+    fn test_updating_same_keyword_keeps_matrix() {
+        let mut pf = Playfair::new("playfair example");
+        pf.matrix();
+
+        // The built matrix survives an update to an equivalent keyword
+        pf.update_keyword("Playfair Example!");
+        assert!(pf.matrix.get().is_some());
+
+        pf.update_keyword("monarchy");
+        assert!(pf.matrix.get().is_none());
+        assert_eq!(pf.matrix(), &Keyword::new("monarchy").to_matrix());
+    }
 }
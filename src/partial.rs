@@ -0,0 +1,59 @@
+//! Matrices that are still being filled in, e.g. by a student building the grid by hand.
+
+use crate::{Keyword, Matrix, MatrixError, Position};
+
+/// A matrix with holes, indexed the same way as a [Matrix], where `None` marks a cell that hasn't
+/// been filled in yet. Once every cell holds a letter it can be checked and turned into a
+/// [Matrix] with [PartialMatrix::to_matrix].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PartialMatrix(pub [[Option<char>; 5]; 5]);
+
+impl PartialMatrix {
+    /// Create a partial matrix with every cell empty.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Place a letter at a position, returning the letter that was there before, if any.
+    pub fn place(&mut self, (x, y): Position, c: char) -> Option<char> {
+        self.0[x][y].replace(c)
+    }
+
+    /// Empty the cell at a position, returning the letter that was there, if any.
+    pub fn remove(&mut self, (x, y): Position) -> Option<char> {
+        self.0[x][y].take()
+    }
+
+    /// Get the letter at a position, if one has been placed.
+    pub fn get(&self, (x, y): Position) -> Option<char> {
+        self.0[x][y]
+    }
+
+    /// Check whether every cell has been filled in. This doesn't check that the letters make a
+    /// valid matrix, see [PartialMatrix::to_matrix] for that.
+    pub fn is_complete(&self) -> bool {
+        self.0.iter().flatten().all(Option::is_some)
+    }
+
+    /// Convert into a lowercase [Matrix], succeeding only when every cell is filled and each
+    /// letter of the matrix alphabet appears exactly once. The first empty cell in fill order is
+    /// reported as [MatrixError::EmptyCell].
+    pub fn to_matrix(&self) -> Result<Matrix, MatrixError> {
+        let mut cells = ['\0'; 25];
+
+        for (idx, cell) in cells.iter_mut().enumerate() {
+            let pos = (idx % 5, idx / 5);
+            *cell = self.get(pos).ok_or(MatrixError::EmptyCell(pos))?;
+        }
+
+        Ok(Keyword::from_cells(cells)?.to_matrix())
+    }
+}
+
+impl From<Matrix> for PartialMatrix {
+    /// Fill every cell from a complete matrix, e.g. to blank out some of its letters for an
+    /// exercise.
+    fn from(matrix: Matrix) -> Self {
+        Self(matrix.map(|column| column.map(Some)))
+    }
+}
@@ -0,0 +1,69 @@
+//! Encrypting with a schedule of keys, switching to the next key every so many characters.
+
+use crate::{Cipher, Keyword, Playfair, DEFAULT_PAD};
+
+/// A cipher for a protocol that rekeys every `chunk_len` characters, cycling through a list of
+/// keys. Both ends need the same keys, in the same order, and the same chunk length.
+///
+/// The plaintext is normalized and split into bigrams as a whole, as for [Playfair], and then
+/// the bigram starting at character `n` of the result (seperators and padding included) is
+/// encrypted with key `n / chunk_len`, wrapping around to the first key after the last. Since
+/// encryption maps bigrams to bigrams, the same schedule lines up with the ciphertext for
+/// decryption. With an odd chunk length, a bigram straddling a boundary uses the key of its
+/// first letter.
+pub struct RekeyingPlayfair {
+    /// A cipher for each key, in the order they are used.
+    ciphers: Vec<Playfair>,
+    /// The number of characters encrypted with each key before switching to the next.
+    chunk_len: usize,
+}
+
+impl RekeyingPlayfair {
+    /// Create a cipher that switches to the next of `keys` every `chunk_len` characters.
+    ///
+    /// # Panics
+    /// If there are no keys, or the chunk length is 0.
+    pub fn new(keys: Vec<Keyword>, chunk_len: usize) -> Self {
+        assert!(!keys.is_empty(), "at least one key is needed");
+        assert!(chunk_len > 0, "the chunk length must be positive");
+
+        Self {
+            ciphers: keys.into_iter().map(Playfair::from_keyword).collect(),
+            chunk_len,
+        }
+    }
+
+    /// The cipher for the bigram at the given index.
+    fn cipher_for(&self, bigram_idx: usize) -> &Playfair {
+        &self.ciphers[(2 * bigram_idx / self.chunk_len) % self.ciphers.len()]
+    }
+}
+
+impl Cipher for RekeyingPlayfair {
+    /// Encrypt a plaintext, switching keys along the way.
+    fn encrypt(&self, plaintext: &str) -> String {
+        Playfair::bigramify(plaintext)
+            .enumerate()
+            .flat_map(|(idx, bigram)| {
+                let (a, b) = self.cipher_for(idx).encrypt_bigram(bigram);
+                [a, b]
+            })
+            .collect()
+    }
+
+    /// Decrypt a ciphertext, switching keys along the way.
+    fn decrypt(&self, ciphertext: &str) -> String {
+        // Pair the letters as-is, as the key schedule relies on every bigram staying in place
+        let letters: Vec<char> = Playfair::normalize(ciphertext).collect();
+
+        letters
+            .chunks(2)
+            .enumerate()
+            .flat_map(|(idx, pair)| {
+                let bigram = (pair[0], *pair.get(1).unwrap_or(&DEFAULT_PAD));
+                let (a, b) = self.cipher_for(idx).decrypt_bigram(bigram);
+                [a, b]
+            })
+            .collect()
+    }
+}
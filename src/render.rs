@@ -0,0 +1,93 @@
+//! A render-agnostic description of the matrix, for drawing it as an SVG, a printed teaching aid,
+//! or anything else, without the crate depending on a renderer.
+
+use crate::{Matrix, Playfair, Position, SubstitutionCase};
+
+/// A single cell of a [GridLayout].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridCell {
+    /// The row of the cell, from 0 at the top.
+    pub row: usize,
+    /// The column of the cell, from 0 at the left.
+    pub column: usize,
+    /// The label of the cell, its column label followed by its row label, e.g. "A1" for the top
+    /// left cell.
+    pub label: String,
+    /// The letter in the cell.
+    pub character: char,
+}
+
+/// The layout of a matrix as a grid of labelled cells. Columns are labelled with letters from
+/// "A", and rows with numbers from "1".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridLayout {
+    /// The labels of the rows, from the top.
+    pub row_labels: Vec<String>,
+    /// The labels of the columns, from the left.
+    pub column_labels: Vec<String>,
+    /// Every cell of the grid, row by row.
+    pub cells: Vec<GridCell>,
+}
+
+/// One frame of an animated encryption, highlighting the cells of a single bigram: the two
+/// plaintext letters and the two ciphertext letters they are replaced by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnimationFrame {
+    /// The rule that moves the letters from the input cells to the output cells.
+    pub case: SubstitutionCase,
+    /// The cells of the plaintext letters, in order.
+    pub inputs: [GridCell; 2],
+    /// The cells of the ciphertext letters, in order.
+    pub outputs: [GridCell; 2],
+}
+
+impl Playfair {
+    /// Describe the matrix as a [GridLayout] of labelled cells, for a renderer to draw.
+    pub fn grid_layout(&self) -> GridLayout {
+        let matrix = self.matrix();
+        let row_labels: Vec<String> = (1..=5).map(|row| row.to_string()).collect();
+        let column_labels: Vec<String> = ('A'..='E').map(String::from).collect();
+
+        let cells = (0..5)
+            .flat_map(|row| (0..5).map(move |column| (row, column)))
+            .map(|(row, column)| grid_cell(matrix, (column, row)))
+            .collect();
+
+        GridLayout {
+            row_labels,
+            column_labels,
+            cells,
+        }
+    }
+
+    /// Describe the encryption of a plaintext as a sequence of [AnimationFrame]s, one for each
+    /// bigram, for a renderer to animate over the [GridLayout]. Drawing the frames is left to the
+    /// caller.
+    pub fn animation_frames(&self, plaintext: &str) -> Vec<AnimationFrame> {
+        let matrix = self.matrix();
+
+        self.bigrams(plaintext)
+            .map(|bigram| {
+                let step = self.trace_bigram(bigram);
+                let cells =
+                    |(a, b): (Position, Position)| [grid_cell(matrix, a), grid_cell(matrix, b)];
+
+                AnimationFrame {
+                    case: step.case,
+                    inputs: cells(step.input_positions),
+                    outputs: cells(step.output_positions),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Describe the cell of the matrix at an `(x, y)` position, labelled like a [GridLayout].
+fn grid_cell(matrix: &Matrix, (column, row): Position) -> GridCell {
+    GridCell {
+        row,
+        column,
+        label: format!("{}{}", (b'A' + column as u8) as char, row + 1),
+        character: matrix[column][row],
+    }
+}
@@ -0,0 +1,81 @@
+//! Encrypting a sequence of messages under a different matrix each, so identical messages give
+//! different ciphertexts.
+
+use crate::{fnv1a, Cipher, Matrix, Playfair};
+
+/// A cipher that perturbs its matrix with a counter for every message, incrementing the counter
+/// each time. The recipient needs the same key and starting counter, and must decrypt the
+/// messages in the order they were encrypted.
+///
+/// The matrix for each message is the key's matrix shuffled by a hash of the counter, and the
+/// options of the key (its rectangle rule, shift and so on) are kept. This hides repeated
+/// messages, but the shuffle is public, so it doesn't make the key any harder to recover.
+pub struct SaltedPlayfair {
+    /// The cipher the per-message matrices are derived from.
+    base: Playfair,
+    /// The counter the next message is encrypted or decrypted with.
+    counter: u64,
+}
+
+impl SaltedPlayfair {
+    /// Wrap a cipher, starting the message counter at `counter`.
+    pub fn new(base: Playfair, counter: u64) -> Self {
+        Self { base, counter }
+    }
+
+    /// The counter the next message will use.
+    pub fn counter(&self) -> u64 {
+        self.counter
+    }
+
+    /// Encrypt the next message, then move on to the next counter.
+    pub fn encrypt(&mut self, plaintext: &str) -> String {
+        let ciphertext = self.salted(self.counter).encrypt(plaintext);
+        self.counter = self.counter.wrapping_add(1);
+
+        ciphertext
+    }
+
+    /// Decrypt the next message, then move on to the next counter.
+    pub fn decrypt(&mut self, ciphertext: &str) -> String {
+        let plaintext = self.salted(self.counter).decrypt(ciphertext);
+        self.counter = self.counter.wrapping_add(1);
+
+        plaintext
+    }
+
+    /// Build the cipher for a single message, with the matrix shuffled by the counter.
+    fn salted(&self, counter: u64) -> Playfair {
+        let base = &self.base;
+        let mut pf = Playfair::decoder_from_matrix(shuffle(base.matrix(), counter))
+            .expect("shuffling keeps each letter exactly once");
+
+        pf.rectangle_rule = base.rectangle_rule;
+        pf.shift = base.shift;
+        pf.bigram_mode = base.bigram_mode;
+        pf.digit_policy = base.digit_policy;
+        pf.separator_strategy = base.separator_strategy;
+        pf.separator_priority.clone_from(&base.separator_priority);
+        pf.fold = base.fold;
+        pf
+    }
+}
+
+/// Shuffle the cells of a matrix with a Fisher-Yates shuffle driven by a SplitMix64 generator,
+/// seeded by a hash of the counter.
+fn shuffle(matrix: &Matrix, counter: u64) -> Matrix {
+    let mut cells: Vec<char> = matrix.iter().flatten().copied().collect();
+    let mut state = fnv1a(&counter.to_le_bytes());
+
+    for idx in (1..cells.len()).rev() {
+        state = state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^= z >> 31;
+
+        cells.swap(idx, (z % (idx as u64 + 1)) as usize);
+    }
+
+    std::array::from_fn(|x| std::array::from_fn(|y| cells[x * 5 + y]))
+}
@@ -0,0 +1,174 @@
+//! Exporting and importing a configured cipher as a URL-safe string, e.g. for putting the
+//! configuration of a web playground in a link.
+
+use crate::{
+    BigramMode, DigitPolicy, MatrixCase, Playfair, RectangleRule, SeparatorStrategy, ShareError,
+    ALPHABET, DEFAULT_SEPARATOR_PRIORITY,
+};
+
+/// The number of option bytes following the matrix in a share string.
+const OPTIONS: usize = 6;
+
+/// The base64url alphabet, see RFC 4648 section 5.
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+impl Playfair {
+    /// Encode the keyword and options of the cipher into a URL-safe token, which can be turned
+    /// back into an identical cipher with [Playfair::from_share_string].
+    ///
+    /// The token is the unpadded base64url encoding of the 25 letters of the matrix in fill order
+    /// and lowercase, followed by a byte for each option:
+    ///
+    /// 1. The [MatrixCase], 0 for lowercase and 1 for uppercase.
+    /// 2. The [RectangleRule], 0 for swapping columns and 1 for swapping rows.
+    /// 3. The [BigramMode], 0 for the standard grouping and 1 for no seperators.
+    /// 4. The shift of the same row and same column rules, from 1 to 4.
+    /// 5. The [DigitPolicy], 0 for dropping digits and 1 for spelling them out.
+    /// 6. The [SeparatorStrategy], 0 for fixed, 1 for context derived and 2 for priority.
+    ///
+    /// If the separator priority list differs from [DEFAULT_SEPARATOR_PRIORITY], its letters
+    /// follow the options as lowercase ASCII.
+    ///
+    /// The matrix is used rather than the keyword so transformed matrices are shared correctly.
+    pub fn to_share_string(&self) -> String {
+        let mut bytes = self.to_bytes().to_ascii_lowercase().to_vec();
+        bytes.push(match self.case {
+            MatrixCase::Lower => 0,
+            MatrixCase::Upper => 1,
+        });
+        bytes.push(match self.rectangle_rule {
+            RectangleRule::SwapColumns => 0,
+            RectangleRule::SwapRows => 1,
+        });
+        bytes.push(match self.bigram_mode {
+            BigramMode::Standard => 0,
+            BigramMode::NoSeparator => 1,
+        });
+        bytes.push(self.shift as u8);
+        bytes.push(match self.digit_policy {
+            DigitPolicy::Drop => 0,
+            DigitPolicy::SpellOut => 1,
+        });
+        bytes.push(match self.separator_strategy {
+            SeparatorStrategy::Fixed => 0,
+            SeparatorStrategy::ContextDerived => 1,
+            SeparatorStrategy::Priority => 2,
+        });
+        if self.separator_priority != DEFAULT_SEPARATOR_PRIORITY {
+            bytes.extend(self.separator_priority.iter().map(|&c| c as u8));
+        }
+
+        encode(&bytes)
+    }
+
+    /// Reconstruct a cipher from a token produced by [Playfair::to_share_string]. Options missing
+    /// from the end of the token take their default value.
+    pub fn from_share_string(share: &str) -> Result<Playfair, ShareError> {
+        let bytes = decode(share).ok_or(ShareError::InvalidEncoding)?;
+
+        if bytes.len() < 25 {
+            return Err(ShareError::InvalidLength(bytes.len()));
+        }
+
+        let (cells, options) = bytes.split_at(25);
+        let (options, priority) = options.split_at(options.len().min(OPTIONS));
+        let mut pf = Playfair::from_bytes(cells.try_into().expect("split at 25 bytes"))?;
+
+        for (idx, &option) in options.iter().enumerate() {
+            match (idx, option) {
+                (0, 0) => pf.set_matrix_case(MatrixCase::Lower),
+                (0, 1) => pf.set_matrix_case(MatrixCase::Upper),
+                (1, 0) => pf.set_rectangle_rule(RectangleRule::SwapColumns),
+                (1, 1) => pf.set_rectangle_rule(RectangleRule::SwapRows),
+                (2, 0) => pf.set_bigram_mode(BigramMode::Standard),
+                (2, 1) => pf.set_bigram_mode(BigramMode::NoSeparator),
+                (3, shift) => pf
+                    .set_shift(shift as usize)
+                    .map_err(|_| ShareError::InvalidOption(shift))?,
+                (4, 0) => pf.set_digit_policy(DigitPolicy::Drop),
+                (4, 1) => pf.set_digit_policy(DigitPolicy::SpellOut),
+                (5, 0) => pf.set_separator_strategy(SeparatorStrategy::Fixed),
+                (5, 1) => pf.set_separator_strategy(SeparatorStrategy::ContextDerived),
+                (5, 2) => pf.set_separator_strategy(SeparatorStrategy::Priority),
+                (_, other) => return Err(ShareError::InvalidOption(other)),
+            }
+        }
+
+        if !priority.is_empty() {
+            // Only lowercase letters of the matrix alphabet are written
+            if let Some(&invalid) = priority.iter().find(|&&b| !ALPHABET.contains(b as char)) {
+                return Err(ShareError::InvalidOption(invalid));
+            }
+            let priority: Vec<char> = priority.iter().map(|&b| b as char).collect();
+            pf.set_separator_priority(&priority)
+                .expect("the letters are in the matrix alphabet");
+        }
+
+        Ok(pf)
+    }
+}
+
+/// Encode bytes as unpadded base64url.
+fn encode(bytes: &[u8]) -> String {
+    let mut buffer = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        // Pack the (up to) 3 bytes into 24 bits, then split those into 6 bit groups
+        let bits = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (idx, &b)| acc | (b as u32) << (16 - 8 * idx));
+
+        // n bytes need n + 1 characters to represent them
+        for idx in 0..=chunk.len() {
+            buffer.push(BASE64URL[(bits >> (18 - 6 * idx) & 0x3f) as usize] as char);
+        }
+    }
+
+    buffer
+}
+
+/// Decode unpadded base64url, giving `None` if the input isn't valid.
+fn decode(input: &str) -> Option<Vec<u8>> {
+    let mut buffer = Vec::with_capacity(input.len() * 3 / 4);
+
+    for chunk in input.as_bytes().chunks(4) {
+        // A single leftover character can't represent a whole byte
+        if chunk.len() == 1 {
+            return None;
+        }
+
+        let mut bits = 0u32;
+        for (idx, c) in chunk.iter().enumerate() {
+            let value = BASE64URL.iter().position(|b| b == c)? as u32;
+            bits |= value << (18 - 6 * idx);
+        }
+
+        // n characters hold n - 1 bytes
+        for idx in 0..chunk.len() - 1 {
+            buffer.push((bits >> (16 - 8 * idx)) as u8);
+        }
+    }
+
+    Some(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64url() {
+        assert_eq!(encode(b""), "");
+        assert_eq!(encode(b"f"), "Zg");
+        assert_eq!(encode(b"fo"), "Zm8");
+        assert_eq!(encode(b"foo"), "Zm9v");
+        assert_eq!(encode(b"foob"), "Zm9vYg");
+        assert_eq!(encode(&[0xfb, 0xff]), "-_8");
+
+        assert_eq!(decode("Zm9vYg"), Some(b"foob".to_vec()));
+        assert_eq!(decode("-_8"), Some(vec![0xfb, 0xff]));
+        assert_eq!(decode("Zm9vY"), None);
+        assert_eq!(decode("Zm9v+g"), None);
+    }
+}
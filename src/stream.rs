@@ -0,0 +1,234 @@
+//! Streaming encryption/decryption over [Read]ers and [Write]rs, for inputs too large to hold in
+//! memory at once.
+
+use std::io::{self, Read, Write};
+
+use crate::{BigramMode, Playfair};
+
+/// The number of bytes read from the input at a time.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+impl Playfair {
+    /// Encrypt plaintext read from `reader` in chunks, writing the ciphertext to `writer` as it
+    /// goes. The output is the same as [Cipher::encrypt](crate::Cipher::encrypt) on the whole
+    /// input, with doubled letters and the padding handled across chunk boundaries.
+    ///
+    /// After each chunk, `progress` is called with the number of bytes processed so far and
+    /// `total`, or 0 if the size of the input isn't known, so callers can draw a progress bar
+    /// without being called back for every byte.
+    pub fn encrypt_reader<R: Read, W: Write>(
+        &self,
+        reader: R,
+        mut writer: W,
+        total: Option<u64>,
+        progress: &mut dyn FnMut(u64, u64),
+    ) -> io::Result<()> {
+        let mut carried = None;
+        let mut processed = 0;
+
+        read_chunks(reader, |chunk| {
+            let mut buffer = String::with_capacity(chunk.len() * 2);
+
+            for c in self.plaintext_chars(chunk) {
+                // Group the letters just like the bigram iterator, but keeping the unpaired
+                // letter around for the next chunk
+                let Some(a) = carried.take() else {
+                    carried = Some(c);
+                    continue;
+                };
+
+                let b = if a != c || self.bigram_mode == BigramMode::NoSeparator {
+                    c
+                } else {
+                    carried = Some(c);
+                    self.separator(a)
+                };
+                let (a, b) = self.encrypt_bigram((a, b));
+
+                buffer.push(a);
+                buffer.push(b);
+            }
+
+            writer.write_all(buffer.as_bytes())?;
+
+            if !chunk.is_empty() {
+                processed += chunk.len() as u64;
+                progress(processed, total.unwrap_or(0));
+            }
+
+            Ok(())
+        })?;
+
+        if let Some(a) = carried {
+            let (a, b) = self.encrypt_bigram((a, self.pad(a)));
+            writer.write_all(&[a as u8, b as u8])?;
+        }
+
+        writer.flush()
+    }
+
+    /// Decrypt ciphertext read from `reader` in chunks, writing the plaintext to `writer` as it
+    /// goes. A letter left over at the end of one chunk is carried over to pair with the first
+    /// letter of the next, so bigrams spanning a chunk boundary are decrypted as normal.
+    ///
+    /// Unlike [Cipher::decrypt](crate::Cipher::decrypt), the ciphertext isn't padded: if it holds
+    /// an odd number of letters, an error of kind [io::ErrorKind::InvalidData] is returned once
+    /// the end of the input is reached (with everything before the last letter already written).
+    pub fn decrypt_reader<R: Read, W: Write>(&self, reader: R, mut writer: W) -> io::Result<()> {
+        let mut carried = None;
+
+        read_chunks(reader, |chunk| {
+            let mut buffer = String::with_capacity(chunk.len());
+
+            for c in Playfair::normalize(chunk) {
+                // Either this letter completes the carried one, or it waits for the next
+                match carried.take() {
+                    Some(a) => {
+                        let (a, b) = self.decrypt_bigram((a, c));
+
+                        buffer.push(a);
+                        buffer.push(b);
+                    }
+                    None => carried = Some(c),
+                }
+            }
+
+            writer.write_all(buffer.as_bytes())
+        })?;
+
+        match carried {
+            Some(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "ciphertext has an odd number of letters",
+            )),
+            None => writer.flush(),
+        }
+    }
+}
+
+/// Read the input in chunks, calling `f` with each chunk as a string. Multibyte characters that
+/// are split across chunks are carried over whole into the next one.
+fn read_chunks<R: Read>(
+    mut reader: R,
+    mut f: impl FnMut(&str) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut buffer = vec![0; CHUNK_SIZE];
+    // The number of bytes at the start of the buffer carried over from the last read
+    let mut carried = 0;
+
+    loop {
+        let read = match reader.read(&mut buffer[carried..]) {
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        let filled = carried + read;
+
+        // Only pass on the valid UTF-8, carrying an incomplete character at the end over to the
+        // next read. Anything else that isn't valid UTF-8 is an error.
+        let valid = match std::str::from_utf8(&buffer[..filled]) {
+            Ok(valid) => valid,
+            Err(e) if e.error_len().is_none() && read > 0 => {
+                std::str::from_utf8(&buffer[..e.valid_up_to()]).expect("prefix is valid UTF-8")
+            }
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        };
+        let used = valid.len();
+
+        f(valid)?;
+
+        if read == 0 {
+            return Ok(());
+        }
+
+        buffer.copy_within(used..filled, 0);
+        carried = filled - used;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Cipher;
+
+    /// A reader that only gives out a few bytes at a time, to exercise chunk boundaries.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = self.0.len().min(buf.len()).min(3);
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn test_decrypt_reader_awkward_boundaries() {
+        let pf = Playfair::new("playfair example");
+        let mut out = Vec::new();
+
+        // Every 3 byte read splits a bigram, and the "—" is split across reads
+        let input = "BMO dz—b xdnabekudmuixmmouvif";
+        pf.decrypt_reader(Trickle(input.as_bytes()), &mut out)
+            .unwrap();
+
+        assert_eq!(out, pf.decrypt(input).as_bytes());
+        assert_eq!(out, b"hidethegoldinthetrexestump");
+    }
+
+    #[test]
+    fn test_encrypt_reader_progress() {
+        let pf = Playfair::new("playfair example");
+        let input = "Hide the gold in the tree stump! ".repeat(CHUNK_SIZE / 10);
+        let mut out = Vec::new();
+        let mut calls = Vec::new();
+
+        pf.encrypt_reader(
+            input.as_bytes(),
+            &mut out,
+            Some(input.len() as u64),
+            &mut |done, total| calls.push((done, total)),
+        )
+        .unwrap();
+
+        assert_eq!(out, pf.encrypt(&input).as_bytes());
+
+        // One call per chunk, ending with everything processed
+        assert_eq!(calls.len(), input.len().div_ceil(CHUNK_SIZE));
+        assert_eq!(
+            calls.last(),
+            Some(&(input.len() as u64, input.len() as u64))
+        );
+    }
+
+    #[test]
+    fn test_encrypt_reader_awkward_boundaries() {
+        let pf = Playfair::new("playfair example");
+        let mut out = Vec::new();
+
+        // Doubled letters and the padding both straddle reads
+        let input = "bal—loo ns";
+        pf.encrypt_reader(
+            Trickle(input.as_bytes()),
+            &mut out,
+            None,
+            &mut |_, total| assert_eq!(total, 0),
+        )
+        .unwrap();
+
+        assert_eq!(out, pf.encrypt(input).as_bytes());
+    }
+
+    #[test]
+    fn test_decrypt_reader_odd_length() {
+        let pf = Playfair::new("playfair example");
+        let mut out = Vec::new();
+
+        let err = pf.decrypt_reader(Trickle(b"bmodz"), &mut out).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(out, b"hide");
+    }
+}
@@ -1,4 +1,16 @@
-use playfair::{Playfair, Cipher};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use playfair::{
+    const_matrix, transpose, AnimationFrame, BigramEvent, BigramMode, BigramStep, Cipher,
+    ConflictPolicy, DecryptError, DecryptUnknownPolicy, DigitPolicy, EncryptStats, FillOrder,
+    FormatOptions, GridCell, KeyRing, KeyRingError, Keyword, KeywordError, Matrix, MatrixCase,
+    MatrixError, MatrixLayout, Neighbors, PartialMatrix, Playfair, Playfair7, PlayfairView,
+    RectangleRule, RekeyingPlayfair, SaltedPlayfair, SeparatorStrategy, ShareError, ShiftError,
+    SubstitutionCase, TransmissionReport, DEFAULT_SEPARATOR, DEFAULT_SEPARATOR_PRIORITY,
+    GRID7_ALPHABET, INDICATOR_SEPARATOR, TEST_VECTORS,
+};
 
 #[test]
 fn test_playfair_wiki() {
@@ -44,3 +56,1557 @@ fn test_updating_keyword() {
     // We also know what the output should be for the second one from the first test
     assert_eq!(enc_2, "bmodzbxdnabekudmuixmmouvif");
 }
+
+#[test]
+fn test_encrypt_chars_matches_encrypt() {
+    let pf = Playfair::new("playfair example");
+    let plaintext = "Hide the gold in the tree stump.";
+
+    let lazy: String = pf.encrypt_chars(plaintext).collect();
+    assert_eq!(lazy, pf.encrypt(plaintext));
+
+    // Only the requested prefix is produced
+    let prefix: String = pf.encrypt_chars(plaintext).take(4).collect();
+    assert_eq!(prefix, "bmod");
+}
+
+#[test]
+fn test_to_coordinates() {
+    let pf = Playfair::new("playfair example");
+
+    // "hi" -> "bm", "de" -> "od"
+    let coords = pf.to_coordinates("hide");
+    assert_eq!(coords, vec![((0, 2), (4, 1)), ((2, 3), (2, 2))]);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_nulls_round_trip() {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let pf = Playfair::new("playfair example");
+    let mut rng = StdRng::seed_from_u64(303);
+
+    let enc = pf.encrypt_with_nulls("hide the gold in the tree stump", 2, &mut rng);
+    assert_eq!(enc.len(), "bmodzbxdnabekudmuixmmouvif".len() + 4);
+    assert_eq!(
+        pf.decrypt_strip_nulls(&enc, 2),
+        "hidethegoldinthetrexestump"
+    );
+
+    // An odd length message also gets padded after the nulls
+    let enc = pf.encrypt_with_nulls("abc", 2, &mut rng);
+    assert_eq!(pf.decrypt_strip_nulls(&enc, 2), "abc");
+
+    // Without nulls, a message ending in the pad letter keeps it
+    let enc = pf.encrypt_with_nulls("exax", 0, &mut rng);
+    assert_eq!(enc, pf.encrypt("exax"));
+    assert_eq!(pf.decrypt_strip_nulls(&enc, 0), "exax");
+
+    // Digits are handled the same way as by the cipher
+    let mut pf = Playfair::new("playfair example");
+    pf.set_digit_policy(DigitPolicy::SpellOut);
+    let enc = pf.encrypt_with_nulls("at 9pm", 1, &mut rng);
+    assert_eq!(pf.decrypt_strip_nulls(&enc, 1), "atninepm");
+}
+
+#[test]
+fn test_uppercase_matrix() {
+    let mut pf = Playfair::new("playfair example");
+    pf.set_matrix_case(MatrixCase::Upper);
+
+    assert_eq!(pf.matrix()[0], ['P', 'I', 'B', 'K', 'T']);
+
+    let enc = pf.encrypt("hide the gold in the tree stump");
+    assert_eq!(enc, "BMODZBXDNABEKUDMUIXMMOUVIF");
+
+    // Either case of ciphertext decrypts
+    assert_eq!(pf.decrypt(&enc), "HIDETHEGOLDINTHETREXESTUMP");
+    assert_eq!(
+        pf.decrypt("bmodzbxdnabekudmuixmmouvif"),
+        "HIDETHEGOLDINTHETREXESTUMP"
+    );
+}
+
+#[test]
+fn test_ciphertext_len() {
+    let pf = Playfair::new("playfair example");
+
+    for plaintext in [
+        "",
+        "a",
+        "hide the gold in the tree stump",
+        "aaaa",
+        "balloon",
+        "Meet at 9!",
+    ] {
+        assert_eq!(pf.ciphertext_len(plaintext), pf.encrypt(plaintext).len());
+    }
+
+    // Without seperators, doubled letters stay together
+    let mut pf = Playfair::new("playfair example");
+    pf.set_bigram_mode(BigramMode::NoSeparator);
+    assert_eq!(pf.ciphertext_len("aa"), 2);
+    assert_eq!(pf.ciphertext_len("aa"), pf.encrypt("aa").len());
+
+    // Spelled out digits take up room in the ciphertext
+    let mut pf = Playfair::new("playfair example");
+    pf.set_digit_policy(DigitPolicy::SpellOut);
+    assert_eq!(pf.ciphertext_len("at 9pm"), 8);
+    assert_eq!(pf.ciphertext_len("at 9pm"), pf.encrypt("at 9pm").len());
+}
+
+#[test]
+fn test_checksum_round_trip() {
+    let pf = Playfair::new("playfair example");
+
+    let enc = pf.encrypt_checked("hide the gold in the tree stump");
+    assert!(enc.starts_with("bmodzbxdnabekudmuixmmouvif"));
+    assert_eq!(enc.len(), 28);
+
+    let dec = pf.decrypt_checked(&enc);
+    assert_eq!(dec, Ok("hidethegoldinthetrexestump".to_string()));
+
+    // Doubled letters, seperators and padding are covered by the checksum as encrypted
+    for (plaintext, decrypted) in [
+        ("box", "boxx"),
+        ("taxx", "taxxxx"),
+        ("balloon", "balxloon"),
+        ("xx", "xxxx"),
+    ] {
+        let enc = pf.encrypt_checked(plaintext);
+        assert_eq!(pf.decrypt_checked(&enc), Ok(decrypted.to_string()));
+    }
+}
+
+#[test]
+fn test_checksum_detects_corruption() {
+    let pf = Playfair::new("playfair example");
+    let enc = pf.encrypt_checked("hide the gold in the tree stump");
+
+    // Flip the first letter of the ciphertext
+    let corrupted = format!("c{}", &enc[1..]);
+    assert_eq!(
+        pf.decrypt_checked(&corrupted),
+        Err(DecryptError::ChecksumMismatch)
+    );
+
+    assert_eq!(pf.decrypt_checked("a"), Err(DecryptError::MissingChecksum));
+}
+
+#[test]
+fn test_encrypt_stable_matches_encrypt() {
+    let pf = Playfair::new("playfair example");
+
+    for plaintext in [
+        "hide the gold in the tree stump",
+        "JaneIsAName",
+        "Lorem ipsum dolor sit amet.",
+    ] {
+        assert_eq!(pf.encrypt_stable(plaintext), pf.encrypt(plaintext));
+    }
+}
+
+#[cfg(feature = "unicode")]
+#[test]
+fn test_accented_input() {
+    let pf = Playfair::new("playfair example");
+
+    assert_eq!(pf.encrypt("café"), pf.encrypt("cafe"));
+    assert_eq!(pf.encrypt("Crème brûlée"), pf.encrypt("creme brulee"));
+    assert_eq!(pf.decrypt(&pf.encrypt("naïf")), "naif");
+
+    // Letters without a Latin mapping are dropped
+    assert_eq!(pf.encrypt("cafe λ"), pf.encrypt("cafe"));
+
+    // Keywords are decomposed the same way
+    assert_eq!(
+        Playfair::new("façade").keyword(),
+        Playfair::new("facade").keyword()
+    );
+}
+
+#[test]
+fn test_encrypt_annotated() {
+    let pf = Playfair::new("playfair example");
+    let steps = pf.encrypt_annotated("hide the gold in the tree stump");
+
+    assert_eq!(steps.len(), 13);
+    assert_eq!(
+        steps[0],
+        BigramStep {
+            input: ('h', 'i'),
+            input_positions: ((4, 2), (0, 1)),
+            case: SubstitutionCase::Rectangle,
+            output: ('b', 'm'),
+            output_positions: ((0, 2), (4, 1)),
+        }
+    );
+
+    let ciphertext: String = steps
+        .iter()
+        .flat_map(|s| [s.output.0, s.output.1])
+        .collect();
+    assert_eq!(ciphertext, "bmodzbxdnabekudmuixmmouvif");
+}
+
+#[test]
+fn test_bytes_round_trip() {
+    let pf = Playfair::new("playfair example");
+    let bytes = pf.to_bytes();
+    assert_eq!(&bytes, b"playfirexmbcdghknoqstuvwz");
+
+    let restored = Playfair::from_bytes(&bytes).unwrap();
+    assert_eq!(restored.keyword(), pf.keyword());
+    assert_eq!(restored.matrix(), pf.matrix());
+    assert_eq!(
+        restored.encrypt("hide the gold in the tree stump"),
+        "bmodzbxdnabekudmuixmmouvif"
+    );
+}
+
+#[test]
+fn test_bytes_validation() {
+    assert_eq!(
+        Playfair::from_bytes(b"playfirexmbcdghknoqstuvwj").err(),
+        Some(MatrixError::InvalidCharacter('j'))
+    );
+    assert_eq!(
+        Playfair::from_bytes(b"playfirexmbcdghknoqstuvwp").err(),
+        Some(MatrixError::DuplicateCharacter('p'))
+    );
+    assert_eq!(
+        Playfair::from_bytes(b"playfirexmbcdghknoqstuvw1").err(),
+        Some(MatrixError::InvalidCharacter('1'))
+    );
+}
+
+#[test]
+fn test_interleaved_round_trip() {
+    let pf = Playfair::new("playfair example");
+
+    let enc = pf.encrypt_interleaved("hide the gold", "meet me at dawn");
+    assert_eq!(enc.len(), 24);
+    // The first bigram comes from the first message, the second from the second
+    assert!(enc.starts_with(&pf.encrypt("hi")));
+    assert_eq!(&enc[2..4], pf.encrypt("me"));
+
+    let (a, b) = pf.decrypt_interleaved(&enc);
+    assert_eq!(a, "hidethegoldx");
+    assert_eq!(b, "meetmeatdawn");
+
+    // The shorter message is padded out
+    let enc = pf.encrypt_interleaved("hide the gold", "run");
+    let (a, b) = pf.decrypt_interleaved(&enc);
+    assert_eq!(a, "hidethegoldx");
+    assert_eq!(b, "runxxxxxxxxx");
+
+    // Each message is grouped like the cipher's own encryption
+    let mut pf = Playfair::new("playfair example");
+    pf.set_digit_policy(DigitPolicy::SpellOut);
+    pf.set_separator_strategy(SeparatorStrategy::Priority);
+    let enc = pf.encrypt_interleaved("xx 9", "at 9pm");
+    let (a, b) = pf.decrypt_interleaved(&enc);
+    assert_eq!(a, pf.decrypt(&pf.encrypt("xx 9")));
+    assert_eq!(b, pf.decrypt(&pf.encrypt("at 9pm")));
+
+    // Without doubling the padding
+    let (_, b) = pf.decrypt_interleaved(&pf.encrypt_interleaved("hide the gold", "run"));
+    assert_eq!(b, "runxxzxzxzxz");
+}
+
+#[test]
+fn test_all_positions() {
+    let pf = Playfair::new("playfair example");
+
+    assert_eq!(pf.all_positions('a'), vec![(2, 0)]);
+    assert_eq!(pf.all_positions('i'), vec![(0, 1)]);
+    assert_eq!(pf.all_positions('j'), pf.all_positions('i'));
+    assert_eq!(pf.all_positions('J'), vec![(0, 1)]);
+    assert!(pf.all_positions('1').is_empty());
+}
+
+#[test]
+fn test_encrypt_selective() {
+    let pf = Playfair::new("playfair example");
+
+    let out = pf.encrypt_selective(
+        "hide the gold [enc]bmodzb[/enc] in the tree stump",
+        "[enc]",
+        "[/enc]",
+    );
+    assert_eq!(
+        out,
+        format!(
+            "{}[enc]bmodzb[/enc]{}",
+            pf.encrypt("hide the gold"),
+            pf.encrypt("in the tree stump")
+        )
+    );
+
+    // An unclosed marker is just text
+    assert_eq!(
+        pf.encrypt_selective("hide [enc]the", "[enc]", "[/enc]"),
+        pf.encrypt("hide [enc]the")
+    );
+}
+
+#[test]
+fn test_share_string_round_trip() {
+    let mut pf = Playfair::new("playfair example");
+    pf.set_matrix_case(MatrixCase::Upper);
+
+    let share = pf.to_share_string();
+    assert!(share
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+
+    let restored = Playfair::from_share_string(&share).unwrap();
+    assert_eq!(restored.keyword(), pf.keyword());
+    assert_eq!(restored.matrix(), pf.matrix());
+
+    assert_eq!(
+        Playfair::from_share_string("not base64!").err(),
+        Some(ShareError::InvalidEncoding)
+    );
+    assert_eq!(
+        Playfair::from_share_string("cGxheQ").err(),
+        Some(ShareError::InvalidLength(4))
+    );
+
+    // Digits are handled the same way after sharing
+    pf.set_digit_policy(DigitPolicy::SpellOut);
+    let restored = Playfair::from_share_string(&pf.to_share_string()).unwrap();
+    assert_eq!(restored.encrypt("at 9pm"), pf.encrypt("at 9pm"));
+    assert_eq!(restored.encrypt("at 9pm").len(), 8);
+
+    // As are doubled letters
+    for strategy in [
+        SeparatorStrategy::ContextDerived,
+        SeparatorStrategy::Priority,
+    ] {
+        pf.set_separator_strategy(strategy);
+        let restored = Playfair::from_share_string(&pf.to_share_string()).unwrap();
+        assert_eq!(restored.encrypt("xx"), pf.encrypt("xx"));
+        assert_ne!(
+            restored.encrypt("xx").to_lowercase(),
+            Playfair::new("playfair example").encrypt("xx")
+        );
+    }
+
+    // Including a custom separator priority list
+    let default_priority = pf.encrypt("xx 9");
+    pf.set_separator_priority(&['q', 'k']).unwrap();
+    let restored = Playfair::from_share_string(&pf.to_share_string()).unwrap();
+    assert_eq!(restored.encrypt("xx 9"), pf.encrypt("xx 9"));
+    assert_ne!(restored.encrypt("xx 9"), default_priority);
+    assert!(restored == pf);
+}
+
+#[test]
+fn test_with_transform() {
+    // Reverse every row of the matrix, which is stored column by column
+    let pf = Playfair::with_transform("playfair example", |m| m.reverse());
+    let plain = Playfair::new("playfair example");
+
+    assert!(!pf.is_keyword_canonical());
+    assert!(plain.is_keyword_canonical());
+    assert_eq!(pf.matrix()[0], plain.matrix()[4]);
+    assert_ne!(pf.matrix(), plain.matrix());
+
+    let enc = pf.encrypt("hide the gold in the tree stump");
+    assert_ne!(enc, plain.encrypt("hide the gold in the tree stump"));
+    assert_eq!(pf.decrypt(&enc), "hidethegoldinthetrexestump");
+
+    // The transformation survives sharing
+    let shared = Playfair::from_share_string(&pf.to_share_string()).unwrap();
+    assert_eq!(shared.matrix(), pf.matrix());
+}
+
+#[test]
+fn test_case_for() {
+    let pf = Playfair::new("playfair example");
+
+    // 'p' and 'i' share the first column, 'p' and 'l' the first row
+    assert_eq!(pf.case_for(('p', 'i')), SubstitutionCase::SameColumn);
+    assert_eq!(pf.case_for(('p', 'l')), SubstitutionCase::SameRow);
+    assert_eq!(pf.case_for(('h', 'i')), SubstitutionCase::Rectangle);
+    assert_eq!(pf.case_for(('P', 'J')), SubstitutionCase::SameColumn);
+}
+
+#[test]
+fn test_encrypt_with_observer() {
+    let pf = Playfair::new("playfair example");
+    let mut events = Vec::new();
+
+    let enc = pf.encrypt_with_observer("hide the gold in the tree stump", &mut |e| events.push(e));
+
+    assert_eq!(enc, "bmodzbxdnabekudmuixmmouvif");
+    assert_eq!(events.len(), enc.len() / 2);
+    assert_eq!(
+        events[0],
+        BigramEvent {
+            input: ('h', 'i'),
+            case: SubstitutionCase::Rectangle,
+            output: ('b', 'm'),
+        }
+    );
+}
+
+#[test]
+fn test_decoder_from_matrix() {
+    let pf = Playfair::new("playfair example");
+    let decoder = Playfair::decoder_from_matrix(*pf.matrix()).unwrap();
+
+    assert_eq!(decoder.keyword(), "playfirexmbcdghknoqstuvwz");
+    assert_eq!(
+        decoder.decrypt(&pf.encrypt("hide the gold in the tree stump")),
+        "hidethegoldinthetrexestump"
+    );
+
+    let mut matrix = *pf.matrix();
+    matrix[0][0] = 'i';
+    assert_eq!(
+        Playfair::decoder_from_matrix(matrix).err(),
+        Some(MatrixError::DuplicateCharacter('i'))
+    );
+
+    // A single case is kept, but mixing them is rejected
+    let upper = pf
+        .matrix()
+        .map(|column| column.map(|c| c.to_ascii_uppercase()));
+    let decoder = Playfair::decoder_from_matrix(upper).unwrap();
+    assert_eq!(decoder.encrypt("hi"), "BM");
+
+    let mut matrix = *pf.matrix();
+    matrix[2][1] = 'E';
+    assert_eq!(
+        Playfair::decoder_from_matrix(matrix).err(),
+        Some(MatrixError::MixedCase((2, 1)))
+    );
+}
+
+#[test]
+fn test_rectangle_rules() {
+    let mut pf = Playfair::new("playfair example");
+
+    // 'h' and 'i' sit on opposite corners of a rectangle
+    assert_eq!(pf.encrypt("hi"), "bm");
+    assert_eq!(pf.decrypt("bm"), "hi");
+
+    pf.set_rectangle_rule(RectangleRule::SwapRows);
+    assert_eq!(pf.encrypt("hi"), "mb");
+    assert_eq!(pf.decrypt("mb"), "hi");
+
+    // The other rules are untouched, and everything still round trips
+    assert_eq!(pf.encrypt("pl"), "la");
+    let enc = pf.encrypt("hide the gold in the tree stump");
+    assert_eq!(pf.encrypt_stable("hide the gold in the tree stump"), enc);
+    assert_eq!(pf.decrypt(&enc), "hidethegoldinthetrexestump");
+
+    let shared = Playfair::from_share_string(&pf.to_share_string()).unwrap();
+    assert_eq!(shared.encrypt("hi"), "mb");
+}
+
+#[test]
+fn test_generate_test_vectors() {
+    let pf = Playfair::new("playfair example");
+    let vectors = pf.generate_test_vectors(&["hide the gold in the tree stump", "hello world"]);
+
+    assert_eq!(vectors.len(), 2);
+    assert_eq!(vectors[0].1, "bmodzbxdnabekudmuixmmouvif");
+
+    for (plaintext, ciphertext) in vectors {
+        // The decryption is the normalized plaintext, with the seperators/padding added
+        let dec = pf.decrypt(&ciphertext);
+        assert_eq!(pf.encrypt(&dec), ciphertext);
+        assert_eq!(dec.replace('x', ""), plaintext.replace(' ', ""));
+    }
+}
+
+#[test]
+fn test_canonical_test_vectors() {
+    for (keyword, plaintext, ciphertext) in TEST_VECTORS {
+        let pf = Playfair::new(keyword);
+
+        assert_eq!(pf.encrypt(plaintext), *ciphertext);
+    }
+}
+
+#[test]
+fn test_try_update_keyword() {
+    let mut pf = Playfair::new("playfair example");
+    let before = pf.encrypt("hide the gold in the tree stump");
+
+    assert_eq!(pf.try_update_keyword("123"), Err(KeywordError::NoLetters));
+    assert_eq!(pf.try_update_keyword(""), Err(KeywordError::NoLetters));
+    assert_eq!(pf.encrypt("hide the gold in the tree stump"), before);
+
+    let previous = pf.try_update_keyword("nonsense").unwrap();
+    assert_eq!(previous, Keyword::new("playfair example"));
+    assert_ne!(pf.encrypt("hide the gold in the tree stump"), before);
+}
+
+#[test]
+fn test_fingerprint() {
+    let pf = Playfair::new("playfair example");
+    let other = Playfair::new("nonsense");
+
+    assert_eq!(
+        pf.fingerprint(),
+        Playfair::new("playfair example").fingerprint()
+    );
+    assert_ne!(pf.fingerprint(), other.fingerprint());
+
+    let (enc, fingerprint) = pf.encrypt_with_fingerprint("hide the gold in the tree stump");
+    assert_eq!(enc, "bmodzbxdnabekudmuixmmouvif");
+    assert_eq!(fingerprint, pf.fingerprint());
+
+    let mut upper = Playfair::new("playfair example");
+    upper.set_matrix_case(MatrixCase::Upper);
+    assert_eq!(upper.fingerprint(), pf.fingerprint());
+}
+
+#[test]
+fn test_boustrophedon_layout() {
+    let mut pf = Playfair::new("playfair example");
+    pf.set_matrix_layout(MatrixLayout::Boustrophedon);
+
+    // The rows are "playf", "mxeri", "bcdgh", "sqonk", "tuvwz"
+    assert_eq!(
+        *pf.matrix(),
+        [
+            ['p', 'm', 'b', 's', 't'],
+            ['l', 'x', 'c', 'q', 'u'],
+            ['a', 'e', 'd', 'o', 'v'],
+            ['y', 'r', 'g', 'n', 'w'],
+            ['f', 'i', 'h', 'k', 'z']
+        ]
+    );
+    assert_eq!(pf.all_positions('m'), vec![(0, 1)]);
+
+    let enc = pf.encrypt("hide the gold in the tree stump");
+    assert_ne!(enc, "bmodzbxdnabekudmuixmmouvif");
+    assert_eq!(pf.decrypt(&enc), "hidethegoldinthetrexestump");
+
+    // Switching back restores the usual matrix
+    pf.set_matrix_layout(MatrixLayout::Rows);
+    assert_eq!(pf.matrix(), Playfair::new("playfair example").matrix());
+}
+
+#[test]
+fn test_reverse_fill_order() {
+    let pf = Playfair::with_fill_order("play", FillOrder::Reverse);
+    assert_eq!(pf.keyword(), "playzxwvutsrqonmkihgfedcb");
+
+    let enc = pf.encrypt("hide the gold");
+    assert_ne!(enc, Playfair::new("play").encrypt("hide the gold"));
+    assert_eq!(pf.decrypt(&enc), "hidethegoldx");
+}
+
+#[test]
+fn test_lossiness_distance() {
+    let pf = Playfair::new("playfair example");
+
+    assert_eq!(pf.lossiness_distance("attack"), 0);
+    assert_eq!(pf.lossiness_distance("Hide the gold!"), 1);
+    // The doubled 'l' gains a seperator
+    assert_eq!(pf.lossiness_distance("hello"), 1);
+    // The 'j' becomes an 'i', and the doubled 'z' gains a seperator and padding
+    assert_eq!(pf.lossiness_distance("jazz"), 3);
+
+    // Spelled out digits are part of the message, not lost or inserted
+    let mut pf = Playfair::new("playfair example");
+    pf.set_digit_policy(DigitPolicy::SpellOut);
+    assert_eq!(pf.lossiness_distance("at 9pm"), 0);
+}
+
+#[test]
+fn test_no_separator_mode() {
+    let mut pf = Playfair::new("playfair example");
+    pf.set_bigram_mode(BigramMode::NoSeparator);
+
+    // The doubled 'e' stays in one bigram, and encrypts to a doubled letter
+    let enc = pf.encrypt("hide the gold in the tree stump");
+    assert_eq!(enc.len(), 26);
+    assert_eq!(&enc[18..20], "dd");
+    assert_eq!(pf.decrypt(&enc), "hidethegoldinthetreestumpx");
+
+    // The mode is part of a shared configuration
+    let shared = Playfair::from_share_string(&pf.to_share_string()).unwrap();
+    assert_eq!(shared.encrypt("tree"), pf.encrypt("tree"));
+}
+
+#[test]
+fn test_is_normalized() {
+    assert!(Playfair::is_normalized("hidethegoldinthetrexestump"));
+    assert!(Playfair::is_normalized(""));
+
+    assert!(!Playfair::is_normalized("hide the gold"));
+    assert!(!Playfair::is_normalized("Hidethegold"));
+    assert!(!Playfair::is_normalized("jane"));
+    assert!(!Playfair::is_normalized("café"));
+}
+
+#[test]
+fn test_partial_matrix() {
+    let matrix = *Playfair::new("playfair example").matrix();
+    let mut partial = PartialMatrix::from(matrix);
+    assert_eq!(partial.to_matrix(), Ok(matrix));
+
+    // Blank out the 'e' and the matrix is incomplete
+    assert_eq!(partial.remove((2, 1)), Some('e'));
+    assert!(!partial.is_complete());
+    assert_eq!(partial.to_matrix(), Err(MatrixError::EmptyCell((2, 1))));
+
+    // Filling it with a letter already in the matrix isn't valid either
+    assert_eq!(partial.place((2, 1), 'p'), None);
+    assert!(partial.is_complete());
+    assert_eq!(
+        partial.to_matrix(),
+        Err(MatrixError::DuplicateCharacter('p'))
+    );
+
+    assert_eq!(partial.place((2, 1), 'E'), Some('p'));
+    assert_eq!(partial.to_matrix(), Ok(matrix));
+
+    assert_eq!(
+        PartialMatrix::new().to_matrix(),
+        Err(MatrixError::EmptyCell((0, 0)))
+    );
+}
+
+#[test]
+fn test_encrypt_formatted() {
+    let pf = Playfair::new("playfair example");
+    let plaintext = "hide the gold in the tree stump";
+
+    assert_eq!(
+        pf.encrypt_formatted(plaintext, FormatOptions::default()),
+        pf.encrypt(plaintext)
+    );
+
+    let five = FormatOptions {
+        uppercase: true,
+        group_size: Some(5),
+        ..FormatOptions::default()
+    };
+    let enc = pf.encrypt_formatted(plaintext, five);
+    assert_eq!(enc, "BMODZ BXDNA BEKUD MUIXM MOUVI F");
+    assert_eq!(pf.decrypt(&enc), "hidethegoldinthetrexestump");
+
+    let pairs = FormatOptions {
+        group_size: Some(2),
+        group_sep: '-',
+        ..FormatOptions::default()
+    };
+    let enc = pf.encrypt_formatted("hide the gold", pairs);
+    assert_eq!(enc, "bm-od-zb-xd-na-ge");
+    assert_eq!(pf.decrypt(&enc), "hidethegoldx");
+}
+
+#[test]
+fn test_playfair7_round_trip() {
+    assert_eq!(GRID7_ALPHABET.chars().count(), 49);
+
+    let pf = Playfair7::new("playfair example");
+    // The space of the keyword is part of the alphabet too
+    assert_eq!(pf.matrix()[0], ['p', ' ', 'g', 's', '1', '8', '"']);
+
+    let enc = pf.encrypt("hello, world. 42!");
+    assert_eq!(enc.chars().count(), 18);
+    assert_eq!(pf.decrypt(&enc), "hello, world. 42!");
+
+    // Uppercase is lowercased, and anything outside of the alphabet is dropped
+    assert_eq!(pf.decrypt(&pf.encrypt("Jazz £5")), "jazz 5");
+    // Including in the ciphertext
+    assert_eq!(pf.decrypt(&enc.to_uppercase()), "hello, world. 42!");
+}
+
+#[test]
+fn test_digit_policy_spell_out() {
+    let mut pf = Playfair::new("playfair example");
+    assert_eq!(pf.decrypt(&pf.encrypt("at 9pm")), "atpm");
+
+    pf.set_digit_policy(DigitPolicy::SpellOut);
+    assert_eq!(pf.decrypt(&pf.encrypt("at 9pm")), "atninepm");
+    assert_eq!(pf.decrypt(&pf.encrypt("42")), "fourtwox");
+}
+
+#[test]
+fn test_matrix_vec() {
+    let grid = Playfair::new("playfair example").matrix_vec();
+    assert_eq!(grid.len(), 5);
+    assert!(grid.iter().all(|column| column.len() == 5));
+    assert_eq!(grid[2][1], 'e');
+
+    let grid = Playfair7::new("playfair example").matrix_vec();
+    assert_eq!(grid.len(), 7);
+    assert!(grid.iter().all(|column| column.len() == 7));
+    assert_eq!(grid[6][6], '_');
+}
+
+#[test]
+fn test_encrypt_avoiding() {
+    let pf = Playfair::new("playfair example");
+    let plaintext = "hide the gold in the tree stump";
+
+    // 'x' appears in the usual ciphertext, but not once it is forbidden
+    assert!(pf.encrypt(plaintext).contains('x'));
+    let enc = pf.encrypt_avoiding(plaintext, &['x', 'M']);
+    assert!(!enc.contains('x') && !enc.contains('m'));
+
+    // The message is still there in order, amongst the fillers
+    let dec = pf.decrypt(&enc);
+    let mut letters = dec.chars();
+    assert!("hidethegoldinthetreestump"
+        .chars()
+        .all(|c| letters.any(|d| d == c)));
+
+    // Without anything to avoid, this is the usual encryption
+    assert_eq!(pf.encrypt_avoiding(plaintext, &[]), pf.encrypt(plaintext));
+
+    // Whatever the cipher's options
+    for strategy in [
+        SeparatorStrategy::ContextDerived,
+        SeparatorStrategy::Priority,
+    ] {
+        let mut pf = Playfair::new("playfair example");
+        pf.set_separator_strategy(strategy);
+        pf.set_digit_policy(DigitPolicy::SpellOut);
+        for plaintext in [plaintext, "xx 9", "balloon x"] {
+            assert_eq!(pf.encrypt_avoiding(plaintext, &[]), pf.encrypt(plaintext));
+        }
+    }
+    let mut pf = Playfair::new("playfair example");
+    pf.set_bigram_mode(BigramMode::NoSeparator);
+    assert_eq!(pf.encrypt_avoiding("balloon", &[]), pf.encrypt("balloon"));
+}
+
+#[test]
+fn test_worked_example() {
+    let pf = Playfair::new("playfair example");
+    let example = pf.worked_example("hide the gold in the tree stump");
+    let lines: Vec<&str> = example.lines().collect();
+
+    assert_eq!(lines.len(), 2 + 13);
+    assert_eq!(lines[0], "| Bigram | Positions | Rule | Output |");
+    assert_eq!(lines[2], "| hi | (4, 2) (0, 1) | rectangle | bm |");
+    assert_eq!(lines[3], "| de | (2, 2) (2, 1) | same column | od |");
+    assert_eq!(lines[11], "| ex | (2, 1) (3, 1) | same row | xm |");
+}
+
+#[test]
+fn test_estimate_recovery_fidelity() {
+    let pf = Playfair::new("playfair example");
+
+    assert_eq!(pf.estimate_recovery_fidelity(&pf.encrypt("attack")), 1.0);
+    assert_eq!(pf.estimate_recovery_fidelity(""), 1.0);
+
+    // "hidethegoldinthetrexestump" has two 'i's and a seperated pair of 'e's
+    let wiki = pf.estimate_recovery_fidelity(&pf.encrypt("hide the gold in the tree stump"));
+    assert_eq!(wiki, 1.0 - 3.0 / 26.0);
+
+    let many_is = pf.estimate_recovery_fidelity(&pf.encrypt("jim is in mississippi"));
+    assert!(many_is < wiki);
+}
+
+#[test]
+fn test_verify_matrix_invertible() {
+    let mut pf = Playfair::new("playfair example");
+    assert!(pf.verify_matrix_invertible());
+
+    pf.set_matrix_case(MatrixCase::Upper);
+    pf.set_rectangle_rule(RectangleRule::SwapRows);
+    assert!(pf.verify_matrix_invertible());
+
+    assert!(Playfair::with_transform("monarchy", |m| m.reverse()).verify_matrix_invertible());
+}
+
+#[test]
+fn test_encrypt_debug() {
+    let pf = Playfair::new("playfair example");
+
+    assert_eq!(
+        pf.encrypt_debug("hide the gold in the tree stump"),
+        "bm# od| zb# xd# na# be# ku# dm# ui# xm- mo# uv- if#"
+    );
+    assert_eq!(pf.encrypt_debug(""), "");
+}
+
+#[test]
+fn test_key_ring_merge() {
+    let rings = || {
+        let mut ours = KeyRing::new();
+        ours.insert("alice", Playfair::new("monarchy"));
+        ours.insert("bob", Playfair::new("playfair example"));
+
+        let mut theirs = KeyRing::new();
+        theirs.insert("alice", Playfair::new("keyword"));
+        theirs.insert("bob", Playfair::new("Playfair Example"));
+        theirs.insert("carol", Playfair::new("charles"));
+
+        (ours, theirs)
+    };
+    let alice = |ring: &KeyRing| ring.get("alice").unwrap().keyword().to_string();
+
+    // Only "alice" conflicts, "bob" has the same key in both
+    let (mut ours, theirs) = rings();
+    assert_eq!(
+        ours.merge(theirs, ConflictPolicy::Keep),
+        Ok(vec!["alice".to_string()])
+    );
+    assert_eq!(alice(&ours), Playfair::new("monarchy").keyword());
+    assert_eq!(ours.labels().collect::<Vec<_>>(), ["alice", "bob", "carol"]);
+
+    let (mut ours, theirs) = rings();
+    assert_eq!(
+        ours.merge(theirs, ConflictPolicy::Overwrite),
+        Ok(vec!["alice".to_string()])
+    );
+    assert_eq!(alice(&ours), Playfair::new("keyword").keyword());
+    assert_eq!(ours.len(), 3);
+
+    let (mut ours, theirs) = rings();
+    assert_eq!(
+        ours.merge(theirs, ConflictPolicy::Error),
+        Err(KeyRingError::Conflict(vec!["alice".to_string()]))
+    );
+    assert_eq!(alice(&ours), Playfair::new("monarchy").keyword());
+    assert_eq!(ours.len(), 2);
+
+    // The same matrix with different options is a different key
+    let mut ours = KeyRing::new();
+    ours.insert("dave", Playfair::new("monarchy"));
+    let mut theirs = KeyRing::new();
+    let mut pf = Playfair::new("monarchy");
+    pf.set_digit_policy(DigitPolicy::SpellOut);
+    theirs.insert("dave", pf);
+
+    assert_eq!(
+        ours.merge(theirs, ConflictPolicy::Overwrite),
+        Ok(vec!["dave".to_string()])
+    );
+    assert_eq!(
+        ours.get("dave").unwrap().encrypt("1"),
+        Playfair::new("monarchy").encrypt("one")
+    );
+}
+
+#[test]
+fn test_with_fold() {
+    // Merge 'k' into 'c' as well as 'j' into 'i', and spell out the ampersand
+    let pf = Playfair::with_fold("kick back", |c| match c.to_ascii_lowercase() {
+        'k' => Some('c'),
+        'j' => Some('i'),
+        '&' => Some('n'),
+        c @ 'a'..='z' => Some(c),
+        _ => None,
+    });
+
+    assert_eq!(pf.keyword(), "cibadefghklmnopqrstuvwxyz");
+    assert_eq!(pf.decrypt(&pf.encrypt("Jack & Kate")), "iacxcncate");
+    assert_eq!(pf.encrypt("Kate"), pf.encrypt("cate"));
+}
+
+#[test]
+fn test_encrypt_stats() {
+    let pf = Playfair::new("playfair example");
+
+    assert_eq!(
+        pf.encrypt_stats("Meet at 9!"),
+        EncryptStats {
+            input_len: 10,
+            dropped: 4,
+            fillers_inserted: 0,
+            output_len: 6,
+            ciphertext: pf.encrypt("Meet at 9!"),
+        }
+    );
+
+    // "hidethegoldinthetreestump" gains a seperator between the 'e's and padding
+    let stats = pf.encrypt_stats("hide the gold in the tree stump");
+    assert_eq!(stats.dropped, 6);
+    assert_eq!(stats.fillers_inserted, 1);
+    assert_eq!(stats.output_len, 26);
+}
+
+#[test]
+fn test_decrypt_clean_with_pad() {
+    let pf = Playfair::new("playfair example");
+
+    let enc = pf.encrypt("hide the gold in the tree stump");
+    assert_eq!(pf.decrypt_clean(&enc), "hidethegoldinthetreestump");
+
+    // A sender seperating and padding with 'q' would send "tree" as "treqeq"
+    let enc = pf.encrypt("treqeq");
+    assert_eq!(pf.decrypt_clean(&enc), "treqeq");
+    assert_eq!(pf.decrypt_clean_with_pad(&enc, 'q', 'q'), "tree");
+}
+
+#[test]
+fn test_as_codec() {
+    let pf = Playfair::new("playfair example");
+    let (encrypt, decrypt) = pf.as_codec();
+
+    let messages = ["hide the gold in the tree stump", "attack at dawn"];
+    let encrypted: Vec<String> = messages.iter().map(|m| encrypt(m)).collect();
+
+    assert_eq!(encrypted[0], pf.encrypt(messages[0]));
+    assert_eq!(decrypt(&encrypted[1]), pf.decrypt(&encrypted[1]));
+}
+
+#[test]
+fn test_context_derived_separator() {
+    let mut pf = Playfair::new("playfair example");
+    pf.set_separator_strategy(SeparatorStrategy::ContextDerived);
+
+    // The seperator after 'e' is the 'g' diagonally below it, after 'l' it is the 'e'
+    let enc = pf.encrypt("trees fall");
+    assert_eq!(pf.decrypt(&enc), "tregesfalelx");
+    assert_eq!(pf.decrypt_clean(&enc), "treesfall");
+
+    let enc = pf.encrypt("hide the gold in the tree stump");
+    assert_eq!(pf.decrypt_clean(&enc), "hidethegoldinthetreestump");
+}
+
+#[test]
+fn test_playfair_view() {
+    fn encrypt_all(cipher: impl Cipher, messages: &[&str]) -> Vec<String> {
+        messages.iter().map(|m| cipher.encrypt(m)).collect()
+    }
+
+    let pf = Playfair::new("playfair example");
+    let view = pf.view();
+    let messages = ["hide the gold in the tree stump", "attack at dawn"];
+
+    // Views are Copy, and can be used from several threads at once
+    let encrypted = std::thread::scope(|s| {
+        let handles: Vec<_> = (0..2)
+            .map(|_| s.spawn(move || encrypt_all(view, &messages)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    assert_eq!(encrypted[0], encrypted[1]);
+    assert_eq!(encrypted[0][0], pf.encrypt(messages[0]));
+    assert_eq!(
+        PlayfairView::from(&pf).decrypt(&encrypted[0][1]),
+        "attackatdawn"
+    );
+}
+
+#[test]
+fn test_encrypt_filtered() {
+    let pf = Playfair::new("playfair example");
+    let blocklist = |words: &[&str]| words.iter().map(|w| w.to_string()).collect::<HashSet<_>>();
+
+    let (enc, blocked) =
+        pf.encrypt_filtered("hide the gold in the tree stump", &blocklist(&["BM"]));
+    assert_eq!(enc, "bmodzbxdnabekudmuixmmouvif");
+    assert!(blocked);
+
+    let (_, blocked) = pf.encrypt_filtered("hide the gold", &blocklist(&["zz", ""]));
+    assert!(!blocked);
+}
+
+#[test]
+fn test_shift() {
+    let mut pf = Playfair::new("playfair example");
+    assert_eq!(pf.set_shift(0), Err(ShiftError::OutOfRange(0)));
+    assert_eq!(pf.set_shift(5), Err(ShiftError::OutOfRange(5)));
+
+    pf.set_shift(2).unwrap();
+    // "de" share a column, so move down two rows to "vo" rather than one to "od"
+    let enc = pf.encrypt("hide the gold in the tree stump");
+    assert_eq!(&enc[2..4], "vo");
+    assert_eq!(enc, pf.encrypt_stable("hide the gold in the tree stump"));
+    assert_eq!(pf.decrypt(&enc), "hidethegoldinthetrexestump");
+    assert!(pf.verify_matrix_invertible());
+
+    let shared = Playfair::from_share_string(&pf.to_share_string()).unwrap();
+    assert_eq!(shared.encrypt("hide the gold"), pf.encrypt("hide the gold"));
+}
+
+#[test]
+fn test_grid_layout() {
+    let layout = Playfair::new("playfair example").grid_layout();
+
+    assert_eq!(layout.row_labels, ["1", "2", "3", "4", "5"]);
+    assert_eq!(layout.column_labels, ["A", "B", "C", "D", "E"]);
+    assert_eq!(layout.cells.len(), 25);
+    assert_eq!(
+        layout.cells[7],
+        GridCell {
+            row: 1,
+            column: 2,
+            label: "C2".to_string(),
+            character: 'e',
+        }
+    );
+    assert_eq!(layout.cells[24].label, "E5");
+    assert_eq!(layout.cells[24].character, 'z');
+}
+
+#[test]
+fn test_is_consistent() {
+    let mut pf = Playfair::new("playfair example");
+    assert!(pf.is_consistent());
+
+    pf.set_matrix_case(MatrixCase::Upper);
+    pf.set_matrix_layout(MatrixLayout::Boustrophedon);
+    assert!(pf.is_consistent());
+
+    // A transformation desyncs the matrix from the keyword, unless it changes nothing
+    assert!(!Playfair::with_transform("playfair example", |m| m.reverse()).is_consistent());
+    assert!(Playfair::with_transform("playfair example", |_| {}).is_consistent());
+}
+
+#[test]
+fn test_encrypt_template() {
+    let pf = Playfair::new("playfair example");
+    let enc = pf.encrypt_template("Dear {name}, hide the gold", ('{', '}'));
+
+    assert_eq!(
+        enc,
+        format!(
+            "{}{{name}}{}",
+            pf.encrypt("Dear "),
+            pf.encrypt(", hide the gold")
+        )
+    );
+    let (before, after) = enc.split_once("{name}").unwrap();
+    assert_eq!(pf.decrypt(before), "dear");
+    assert_eq!(pf.decrypt(after), "hidethegoldx");
+}
+
+#[test]
+fn test_canonical_ciphertext() {
+    let mut pf = Playfair::new("playfair example");
+    let plaintext = "hide the gold in the tree stump";
+    let canonical = pf.canonical_ciphertext(plaintext);
+    assert_eq!(canonical, "bmodzbxdnabekudmuixmmouvif");
+
+    let grouped = FormatOptions {
+        uppercase: true,
+        group_size: Some(5),
+        ..FormatOptions::default()
+    };
+    assert_eq!(
+        Playfair::canonicalize(&pf.encrypt_formatted(plaintext, grouped)),
+        canonical
+    );
+
+    pf.set_matrix_case(MatrixCase::Upper);
+    assert_eq!(pf.canonical_ciphertext(plaintext), canonical);
+    assert_eq!(Playfair::canonicalize(&pf.encrypt(plaintext)), canonical);
+}
+
+#[test]
+fn test_from_shared() {
+    let shared = Playfair::new("playfair example").shared_matrix();
+    let a = Playfair::from_shared(Arc::clone(&shared)).unwrap();
+    let b = Playfair::from_shared(Arc::clone(&shared)).unwrap();
+
+    // Both read the same matrix rather than a copy each
+    assert_eq!(Arc::strong_count(&shared), 3);
+    assert!(std::ptr::eq(a.matrix(), b.matrix()));
+    assert_eq!(
+        a.encrypt("hide the gold in the tree stump"),
+        b.encrypt("hide the gold in the tree stump")
+    );
+    assert_eq!(a.keyword(), "playfirexmbcdghknoqstuvwz");
+
+    // Changing one instance copies the matrix, leaving the other alone
+    let mut c = Playfair::from_shared(Arc::clone(&shared)).unwrap();
+    c.set_matrix_layout(MatrixLayout::Boustrophedon);
+    assert_ne!(c.matrix(), &*shared);
+    assert_eq!(b.matrix(), &*shared);
+
+    let mut invalid = *shared;
+    invalid[0][0] = 'l';
+    assert_eq!(
+        Playfair::from_shared(Arc::new(invalid)).err(),
+        Some(MatrixError::DuplicateCharacter('l'))
+    );
+}
+
+#[test]
+fn test_decrypt_robust() {
+    let pf = Playfair::new("playfair example");
+
+    assert_eq!(
+        pf.decrypt_robust("BMO\nDZ-BXD NAB\tEK.UD MUIXM, MOUVI F"),
+        Ok("hidethegoldinthetrexestump".to_string())
+    );
+    assert_eq!(
+        pf.decrypt_robust("bmo dzb x"),
+        Err(DecryptError::OddLength(7))
+    );
+}
+
+#[test]
+fn test_rectangle_rule_orderings() {
+    let columns = Playfair::new("playfair example");
+    let mut rows = Playfair::new("playfair example");
+    rows.set_rectangle_rule(RectangleRule::SwapRows);
+
+    for a in 'a'..='z' {
+        for b in 'a'..='z' {
+            if a == 'j' || b == 'j' || columns.case_for((a, b)) != SubstitutionCase::Rectangle {
+                continue;
+            }
+
+            // The same corners, in the opposite order, each undone by its own rule
+            let (c, d) = columns.map_bigram((a, b));
+            assert_eq!(rows.map_bigram((a, b)), (d, c));
+            assert_eq!(columns.decrypt(&format!("{c}{d}")), format!("{a}{b}"));
+            assert_eq!(rows.decrypt(&format!("{d}{c}")), format!("{a}{b}"));
+        }
+    }
+}
+
+#[test]
+fn test_worst_case_input() {
+    let pf = Playfair::new("playfair example");
+
+    for len in [0, 1, 2, 7, 64] {
+        let input = Playfair::worst_case_input(len);
+        assert_eq!(input.len(), len);
+        assert_eq!(pf.ciphertext_len(&input), 2 * len);
+        assert_eq!(pf.encrypt_stats(&input).fillers_inserted, len);
+    }
+}
+
+#[test]
+fn test_transpose() {
+    let pf = Playfair::new("playfair example");
+    let textbook = transpose(pf.matrix());
+
+    assert_eq!(transpose(&textbook), *pf.matrix());
+    assert_eq!(textbook[0], ['p', 'l', 'a', 'y', 'f']);
+    assert_eq!(textbook[4], ['t', 'u', 'v', 'w', 'z']);
+
+    // A row-major matrix from elsewhere builds the same cipher once transposed
+    assert_eq!(
+        Playfair::decoder_from_matrix(transpose(&textbook))
+            .unwrap()
+            .encrypt("hide the gold"),
+        pf.encrypt("hide the gold")
+    );
+}
+
+#[test]
+fn test_salted_playfair() {
+    let mut sender = SaltedPlayfair::new(Playfair::new("playfair example"), 42);
+    let first = sender.encrypt("hide the gold");
+    let second = sender.encrypt("hide the gold");
+
+    assert_ne!(first, second);
+    assert_eq!(sender.counter(), 44);
+
+    let mut receiver = SaltedPlayfair::new(Playfair::new("playfair example"), 42);
+    assert_eq!(receiver.decrypt(&first), "hidethegoldx");
+    assert_eq!(receiver.decrypt(&second), "hidethegoldx");
+
+    // Starting from the wrong counter gives the wrong matrix
+    let mut wrong = SaltedPlayfair::new(Playfair::new("playfair example"), 43);
+    assert_ne!(wrong.decrypt(&first), "hidethegoldx");
+}
+
+#[test]
+fn test_normalization_table() {
+    let table = Playfair::normalization_table();
+    let lookup = |c| table.iter().find(|(k, _)| *k == c).unwrap().1;
+
+    assert_eq!(lookup('J'), Some('i'));
+    assert_eq!(lookup('j'), Some('i'));
+    assert_eq!(lookup('Q'), Some('q'));
+    assert_eq!(lookup('5'), None);
+    assert_eq!(lookup(' '), None);
+
+    // The table agrees with what encryption actually does
+    let pf = Playfair::new("playfair example");
+    for (c, normalized) in table {
+        let expected = normalized.map(|n| pf.encrypt(&n.to_string()));
+        assert_eq!(
+            Some(pf.encrypt(&c.to_string())).filter(|e| !e.is_empty()),
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_const_matrix() {
+    const KEY: Matrix = const_matrix([
+        'p', 'l', 'a', 'y', 'f', 'i', 'r', 'e', 'x', 'm', 'b', 'c', 'd', 'g', 'h', 'k', 'n', 'o',
+        'q', 's', 't', 'u', 'v', 'w', 'z',
+    ]);
+    let pf = Playfair::from_const_matrix(KEY);
+
+    assert_eq!(KEY, *Playfair::new("playfair example").matrix());
+    assert_eq!(pf.keyword(), "playfirexmbcdghknoqstuvwz");
+    assert_eq!(
+        pf.encrypt("hide the gold in the tree stump"),
+        "bmodzbxdnabekudmuixmmouvif"
+    );
+}
+
+#[test]
+#[should_panic(expected = "duplicate letter")]
+fn test_const_matrix_duplicate() {
+    let mut cells: Vec<char> = "playfirexmbcdghknoqstuvwz".chars().collect();
+    cells[24] = 'a';
+
+    const_matrix(cells.try_into().unwrap());
+}
+
+#[test]
+fn test_encrypt_double() {
+    let first = Playfair::new("playfair example");
+    let second = Playfair::new("monarchy");
+    let plaintext = "hide the gold in the tree stump";
+
+    let ciphertext = first.encrypt_double(plaintext, &second);
+
+    assert_eq!(ciphertext.len(), first.encrypt(plaintext).len());
+    assert_ne!(ciphertext, first.encrypt(plaintext));
+    assert_ne!(ciphertext, second.encrypt(plaintext));
+    assert_eq!(
+        first.decrypt_double(&ciphertext, &second),
+        "hidethegoldinthetrexestump"
+    );
+}
+
+#[test]
+fn test_encrypt_with_indicator() {
+    let pf = Playfair::new("playfair example");
+    let ciphertext = pf.encrypt_with_indicator("hide the gold in the tree stump", "ABCDE");
+
+    // The separator sits between the two parts
+    let (a, b) = pf.map_bigram(INDICATOR_SEPARATOR);
+    assert_eq!(&ciphertext[6..8], format!("{a}{b}"));
+
+    assert_eq!(
+        pf.decrypt_with_indicator(&ciphertext),
+        Some((
+            "abcdex".to_string(),
+            "hidethegoldinthetrexestump".to_string()
+        ))
+    );
+    assert_eq!(pf.decrypt_with_indicator(&pf.encrypt("no indicator")), None);
+}
+
+#[test]
+fn test_throughput_sample() {
+    let pf = Playfair::new("playfair example");
+    let sample = "hide the gold in the tree stump ".repeat(100);
+
+    assert!(pf.throughput_sample(&sample, 10) > Duration::ZERO);
+}
+
+#[test]
+fn test_pack_ciphertext() {
+    let ciphertext = "bmodzbxdnabekudmuixmmouvif";
+    let packed = Playfair::pack_ciphertext(ciphertext);
+
+    // 9 groups of 14 bits, rather than a byte per letter
+    assert_eq!(packed.len(), 16);
+    assert_eq!(Playfair::unpack_ciphertext(&packed, 26), ciphertext);
+    assert_eq!(Playfair::unpack_ciphertext(&packed, 5), "bmodz");
+
+    // Normalized on the way in, so it comes out lowercase
+    let packed = Playfair::pack_ciphertext("ZZ Y");
+    assert_eq!(Playfair::unpack_ciphertext(&packed, 3), "zzy");
+    // Letters that can't be packed are dropped, rather than packed as an 'a'
+    assert_eq!(
+        Playfair::pack_ciphertext("bmøod"),
+        Playfair::pack_ciphertext("bmod")
+    );
+    assert_eq!(Playfair::unpack_ciphertext(&[], 0), "");
+}
+
+#[test]
+fn test_decrypt_with_policy() {
+    let pf = Playfair::new("playfair example");
+    let ciphertext = "bmod zbxd, na5be";
+
+    assert_eq!(
+        pf.decrypt_with_policy(ciphertext, DecryptUnknownPolicy::Drop),
+        Ok(pf.decrypt(ciphertext))
+    );
+    assert_eq!(
+        pf.decrypt_with_policy(ciphertext, DecryptUnknownPolicy::PassThrough),
+        Ok("hidetheg,ol5di".to_string())
+    );
+    assert_eq!(
+        pf.decrypt_with_policy(ciphertext, DecryptUnknownPolicy::Error),
+        Err(DecryptError::UnknownCharacter(','))
+    );
+    assert_eq!(
+        pf.decrypt_with_policy("bmod5", DecryptUnknownPolicy::Error),
+        Err(DecryptError::UnknownCharacter('5'))
+    );
+    assert_eq!(
+        pf.decrypt_with_policy("BM OD", DecryptUnknownPolicy::Error),
+        Ok("hide".to_string())
+    );
+    for policy in [
+        DecryptUnknownPolicy::PassThrough,
+        DecryptUnknownPolicy::Error,
+    ] {
+        assert_eq!(
+            pf.decrypt_with_policy("bmjd", policy),
+            Ok(pf.decrypt("bmid"))
+        );
+        assert_eq!(
+            pf.decrypt_with_policy("BMJD", policy),
+            Ok(pf.decrypt("bmid"))
+        );
+    }
+}
+
+#[test]
+fn test_encrypt_bigrams_explicit() {
+    let pf = Playfair::new("playfair example");
+
+    assert_eq!(
+        pf.encrypt_bigrams_explicit(&[('h', 'i'), ('D', 'E'), ('t', 'h')]),
+        Ok("bmodzb".to_string())
+    );
+
+    // Doubled letters are kept together rather than seperated
+    let (a, b) = pf.map_bigram(('e', 'e'));
+    assert_eq!(
+        pf.encrypt_bigrams_explicit(&[('e', 'e')]),
+        Ok(format!("{a}{b}"))
+    );
+    // The same column rule takes each 'l' to the 'r' below it
+    assert_eq!(
+        pf.encrypt_bigrams_explicit(&[('l', 'l')]),
+        Ok("rr".to_string())
+    );
+    assert_eq!(
+        pf.encrypt_bigrams_explicit(&[('j', 'a')]),
+        pf.encrypt_bigrams_explicit(&[('i', 'a')])
+    );
+
+    assert_eq!(
+        pf.encrypt_bigrams_explicit(&[('h', 'i'), ('4', 'e')]),
+        Err(MatrixError::InvalidCharacter('4'))
+    );
+    assert_eq!(pf.encrypt_bigrams_explicit(&[]), Ok(String::new()));
+}
+
+#[test]
+fn test_vector_hash() {
+    let pf = Playfair::new("playfair example");
+    let hash = pf.vector_hash("hide the gold in the tree stump");
+
+    // A golden value, which only changes if the behavior of the cipher does
+    assert_eq!(hash, 17080893685766177950);
+    assert_ne!(pf.vector_hash("hide the gold in the tree stumps"), hash);
+    assert_ne!(
+        Playfair::new("monarchy").vector_hash("hide the gold in the tree stump"),
+        hash
+    );
+}
+
+#[test]
+fn test_verify_transmission() {
+    let pf = Playfair::new("playfair example");
+    let plaintext = "hide the gold in the tree stump";
+
+    let report = pf.verify_transmission(plaintext, "BMODZ BXDNA BEKUD MUIXM MOUVI F");
+    assert!(report.is_intact());
+
+    // A single letter corrupted in transit
+    let report = pf.verify_transmission(plaintext, "bmodzbxdnabekudmuixmmpuvif");
+    assert!(!report.is_intact());
+    assert_eq!(report.mismatches, vec![21]);
+    assert_eq!(report.expected, "bmodzbxdnabekudmuixmmouvif");
+
+    // A dropped letter shows up at the end as well
+    let report = pf.verify_transmission(plaintext, "bmodzbxdnabekudmuixmmouvi");
+    assert_eq!(
+        report,
+        TransmissionReport {
+            expected: "bmodzbxdnabekudmuixmmouvif".to_string(),
+            received: "bmodzbxdnabekudmuixmmouvi".to_string(),
+            mismatches: vec![25],
+        }
+    );
+}
+
+#[test]
+fn test_neighbors() {
+    let mut pf = Playfair::new("playfair example");
+
+    // 'z' is in the bottom right corner, so both neighbors wrap around
+    assert_eq!(
+        pf.neighbors('z'),
+        Neighbors {
+            right: 't',
+            below: 'f'
+        }
+    );
+    assert_eq!(
+        pf.neighbors('e'),
+        Neighbors {
+            right: 'x',
+            below: 'd'
+        }
+    );
+
+    // They are the targets of the same row and same column rules
+    let Neighbors { right, below } = pf.neighbors('p');
+    assert_eq!(pf.map_bigram(('p', 'a')).0, right);
+    assert_eq!(pf.map_bigram(('p', 'i')).0, below);
+
+    pf.set_shift(2).unwrap();
+    assert_eq!(pf.neighbors('z').right, 'u');
+}
+
+#[test]
+fn test_rekeying_playfair() {
+    let keys = ["playfair example", "monarchy", "charles"];
+    let pf = RekeyingPlayfair::new(keys.iter().map(|kw| Keyword::new(kw)).collect(), 6);
+    let ciphertext = pf.encrypt("hide the gold in the tree stump");
+
+    // Each chunk of 6 letters is encrypted under the next key, wrapping around
+    assert_eq!(&ciphertext[..6], &Playfair::new(keys[0]).encrypt("hideth"));
+    assert_eq!(
+        &ciphertext[6..12],
+        &Playfair::new(keys[1]).encrypt("egoldi")
+    );
+    assert_eq!(
+        &ciphertext[12..18],
+        &Playfair::new(keys[2]).encrypt("nthetr")
+    );
+    assert_eq!(
+        &ciphertext[18..24],
+        &Playfair::new(keys[0]).encrypt("exestu")
+    );
+
+    assert_eq!(pf.decrypt(&ciphertext), "hidethegoldinthetrexestump");
+}
+
+#[test]
+fn test_rule_histogram() {
+    let pf = Playfair::new("playfair example");
+    let plaintext = "hide the gold in the tree stump";
+
+    let (column, row, rectangle) = pf.rule_histogram(plaintext);
+    // "de" shares a column, "ex" and "tu" share a row
+    assert_eq!((column, row, rectangle), (1, 2, 10));
+    assert_eq!(column + row + rectangle, pf.ciphertext_len(plaintext) / 2);
+    assert_eq!(pf.rule_histogram(""), (0, 0, 0));
+}
+
+#[test]
+fn test_likely_has_trailing_pad() {
+    let pf = Playfair::new("playfair example");
+
+    assert!(pf.likely_has_trailing_pad(&pf.encrypt("hide the gold")));
+    assert!(!pf.likely_has_trailing_pad(&pf.encrypt("hide the golds")));
+    assert!(!pf.likely_has_trailing_pad(""));
+
+    // A message genuinely ending in the pad letter can't be told apart
+    assert!(pf.likely_has_trailing_pad(&pf.encrypt("in a box")));
+}
+
+#[test]
+fn test_priority_separator() {
+    let mut pf = Playfair::new("playfair example");
+    pf.set_separator_strategy(SeparatorStrategy::Priority);
+
+    // Neither the seperator nor the padding ever doubles the letter before it
+    for (plaintext, filled) in [("xx", "xzxz"), ("zz", "zxzx"), ("ee", "exex"), ("x", "xz")] {
+        let ciphertext = pf.encrypt(plaintext);
+        let decrypted: Vec<char> = pf.decrypt(&ciphertext).chars().collect();
+
+        assert_eq!(decrypted.iter().collect::<String>(), filled);
+        assert!(decrypted.chunks(2).all(|pair| pair[0] != pair[1]));
+        assert!(pf.likely_has_trailing_pad(&ciphertext));
+        assert_eq!(pf.decrypt_clean(&ciphertext), plaintext);
+    }
+
+    // A genuine trailing 'z' after an 'x' looks like the padding, like a trailing 'x' normally
+    // does
+    let ciphertext = pf.encrypt("xz");
+    assert_eq!(pf.decrypt(&ciphertext), "xz");
+    assert_eq!(pf.decrypt_clean(&ciphertext), "x");
+    assert!(!pf.likely_has_trailing_pad(&pf.encrypt("ez")));
+
+    pf.set_separator_priority(&['Q', 'k']).unwrap();
+    assert_eq!(pf.decrypt(&pf.encrypt("qq")), "qkqx");
+    assert_eq!(pf.decrypt(&pf.encrypt("x")), "xq");
+    assert_eq!(pf.decrypt(&pf.encrypt("ll")), "lqlx");
+    assert_eq!(
+        pf.set_separator_priority(&['x', '-']),
+        Err(MatrixError::InvalidCharacter('-'))
+    );
+
+    assert_eq!(DEFAULT_SEPARATOR_PRIORITY[0], DEFAULT_SEPARATOR);
+}
+
+#[test]
+fn test_animation_frames() {
+    let pf = Playfair::new("playfair example");
+    let plaintext = "hide the gold in the tree stump";
+    let frames = pf.animation_frames(plaintext);
+
+    assert_eq!(frames.len(), pf.ciphertext_len(plaintext) / 2);
+
+    let cell = |label: &str, character| {
+        let mut chars = label.chars();
+        let column = (chars.next().unwrap() as u8 - b'A') as usize;
+        let row = chars.next().unwrap().to_digit(10).unwrap() as usize - 1;
+
+        GridCell {
+            row,
+            column,
+            label: label.to_string(),
+            character,
+        }
+    };
+
+    // "hi" becomes "bm" by the rectangle rule
+    assert_eq!(
+        frames[0],
+        AnimationFrame {
+            case: SubstitutionCase::Rectangle,
+            inputs: [cell("E3", 'h'), cell("A2", 'i')],
+            outputs: [cell("A3", 'b'), cell("E2", 'm')],
+        }
+    );
+    assert!(frames[1..]
+        .iter()
+        .all(|frame| pf.grid_layout().cells.contains(&frame.inputs[0])));
+}
+
+#[test]
+fn test_with_column_key() {
+    let plain = Playfair::new("playfair example");
+    let pf = Playfair::with_column_key("playfair example", "cab");
+
+    // The columns are read out in the order of "cabde": 'a', 'b', 'c', 'd', 'e'
+    let (original, permuted) = (plain.matrix(), pf.matrix());
+    assert_eq!(permuted[0], original[1]);
+    assert_eq!(permuted[1], original[2]);
+    assert_eq!(permuted[2], original[0]);
+    assert_eq!(permuted[3..], original[3..]);
+    assert!(!pf.is_keyword_canonical());
+
+    let ciphertext = pf.encrypt("hide the gold in the tree stump");
+    assert_ne!(ciphertext, plain.encrypt("hide the gold in the tree stump"));
+    assert_eq!(pf.decrypt(&ciphertext), "hidethegoldinthetrexestump");
+
+    assert_eq!(
+        Playfair::with_column_key("playfair example", "").matrix(),
+        original
+    );
+}